@@ -1,8 +1,10 @@
 use std::borrow::Borrow;
 use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fmt;
 use std::str::FromStr;
 
+use auto_ops::impl_op_ex;
 use chrono::{Datelike, Month, NaiveDate, TimeZone};
 use chrono_tz::{Tz, UTC};
 use eyre::{eyre, Result};
@@ -10,6 +12,7 @@ use num_traits::FromPrimitive;
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Serialize};
 
+use crate::duration::Duration;
 use crate::op::{DOp, DateOp};
 use crate::span::endpoint::EndpointConversion;
 use crate::time::Time;
@@ -122,6 +125,7 @@ impl Date {
         self.d.day()
     }
 
+    /// Returns the same date with day |d|, clamped to the days in the month.
     pub fn with_day(&self, d: u32) -> Self {
         for max in (28..=31).rev() {
             if let Some(res) = self.d.with_day(d.clamp(1, max)) {
@@ -131,10 +135,40 @@ impl Date {
         panic!("bug: invalid day {d}");
     }
 
+    /// Last day of the current month.
+    pub fn last_day_of_month(&self) -> Self {
+        self.with_day(31)
+    }
+
     pub fn add_days(&self, d: i32) -> Self {
         Self::new(self.d + chrono::Duration::days(i64::from(d)), self.tz())
     }
 
+    /// Counts occurrences of |day| in [start, end).
+    #[must_use]
+    pub fn weekday_count(start: Date, end: Date, day: Day) -> i64 {
+        let days = (end.d - start.d).num_days();
+        // Days from |start| until the first occurrence of |day|.
+        let offset = (day as i64 - start.weekday() as i64).rem_euclid(7);
+        if offset >= days {
+            0
+        } else {
+            (days - offset - 1) / 7 + 1
+        }
+    }
+
+    /// Counts weekdays in [start, end) which aren't in |holidays|.
+    #[must_use]
+    pub fn business_days_between(start: Date, end: Date, holidays: &HashSet<Date>) -> i64 {
+        let weekdays = [Day::Mon, Day::Tue, Day::Wed, Day::Thu, Day::Fri];
+        let days: i64 = weekdays.iter().map(|&day| Date::weekday_count(start, end, day)).sum();
+        let holidays = holidays
+            .iter()
+            .filter(|h| (start..end).contains(*h) && weekdays.contains(&h.weekday()))
+            .count();
+        days - holidays as i64
+    }
+
     pub fn weekday(&self) -> Day {
         match self.d.weekday() {
             chrono::Weekday::Mon => Day::Mon,
@@ -167,6 +201,9 @@ impl Date {
         Self::new(self.with_day(1).d.with_month(m).unwrap(), self.tz()).with_day(d)
     }
 
+    /// Adds |add_m| months, keeping the day of the month where possible and
+    /// otherwise clamping it to the end of the month, e.g. Jan 31 plus one
+    /// month is the last day of February.
     pub fn add_months(&self, add_m: i32) -> Self {
         let d = self.day();
         let total_m = self.month0() as i32 + add_m;
@@ -188,6 +225,40 @@ impl Date {
     pub fn add_years(&self, y: i32) -> Self {
         self.with_year(self.year() + y)
     }
+
+    /// Quarter of the year, from 1 to 4.
+    #[must_use]
+    pub fn quarter(&self) -> u32 {
+        self.month0() / 3 + 1
+    }
+
+    /// Moves to the same month and day within quarter |q| (1 to 4) of the
+    /// year, clamping the day to the end of the month.
+    pub fn with_quarter(&self, q: u32) -> Self {
+        self.with_month((q.clamp(1, 4) - 1) * 3 + self.month0() % 3 + 1)
+    }
+
+    /// First day of the current quarter.
+    pub fn quarter_start(&self) -> Self {
+        self.with_day(1).with_month((self.quarter() - 1) * 3 + 1)
+    }
+
+    /// Last day of the current quarter.
+    pub fn quarter_end(&self) -> Self {
+        self.quarter_start().add_months(3).add_days(-1)
+    }
+
+    /// The next day. None at chrono's upper bound.
+    #[must_use]
+    pub fn succ(&self) -> Option<Self> {
+        self.to_open(false)
+    }
+
+    /// The previous day. None at chrono's lower bound.
+    #[must_use]
+    pub fn pred(&self) -> Option<Self> {
+        self.to_open(true)
+    }
 }
 
 impl EndpointConversion for Date {
@@ -202,6 +273,9 @@ impl EndpointConversion for Date {
     }
 }
 
+// Whole days between the two dates, ignoring time zones like |Ord| does.
+impl_op_ex!(-|a: &Date, b: &Date| -> Duration { (a.d - b.d).num_days() * Duration::DAY });
+
 impl<'a> Deserialize<'a> for Date {
     fn deserialize<D: serde::Deserializer<'a>>(d: D) -> Result<Self, D::Error> {
         struct DateVisitor;
@@ -236,3 +310,117 @@ impl Serialize for Date {
         s.serialize_str(&(self.fmt("%Y-%m-%d") + " " + self.tz().name()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Australia::Sydney;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::iter::DateIter;
+    use crate::span::exc::SpanExc;
+    use crate::span::inc::SpanInc;
+
+    #[test]
+    fn span() {
+        let d = ymd(2020, 2, 27, UTC);
+        assert_eq!(d.add_days(3) - d, 3 * Duration::DAY);
+        assert_eq!(d - d.add_days(3), -3 * Duration::DAY);
+
+        assert_eq!(SpanInc::new(d, d.add_days(3)).size(), Some(4 * Duration::DAY));
+        assert_eq!(SpanExc::new(d, d.add_days(3)).size(), 3 * Duration::DAY);
+        assert_eq!(SpanInc::new(d, d.add_days(3)).to_exc(), Some(SpanExc::new(d, d.add_days(4))));
+        assert_eq!(SpanExc::new(d, d.add_days(3)).to_inc(), Some(SpanInc::new(d, d.add_days(2))));
+
+        // Endpoint conversion nudges by one day, across month boundaries too.
+        assert_eq!(d.to_open(true), Some(ymd(2020, 2, 26, UTC)));
+        assert_eq!(d.to_open(false), Some(ymd(2020, 2, 28, UTC)));
+        assert_eq!(ymd(2020, 2, 29, UTC).to_closed(true), Some(ymd(2020, 3, 1, UTC)));
+        assert_eq!(ymd(2020, 3, 1, UTC).to_closed(false), Some(ymd(2020, 2, 29, UTC)));
+    }
+
+    #[test]
+    fn weekday_count() {
+        let mon = ymd(2020, 12, 7, UTC);
+        assert_eq!(Date::weekday_count(mon, ymd(2020, 12, 28, UTC), Day::Mon), 3);
+        assert_eq!(Date::weekday_count(mon, ymd(2020, 12, 29, UTC), Day::Mon), 4);
+        assert_eq!(Date::weekday_count(mon.add_days(1), ymd(2020, 12, 28, UTC), Day::Mon), 2);
+        assert_eq!(Date::weekday_count(mon, mon, Day::Mon), 0);
+        assert_eq!(Date::weekday_count(mon, mon.add_days(1), Day::Mon), 1);
+        assert_eq!(Date::weekday_count(mon, mon.add_days(-7), Day::Mon), 0);
+        assert_eq!(Date::weekday_count(ymd(2020, 12, 1, UTC), ymd(2020, 12, 31, UTC), Day::Fri), 4);
+    }
+
+    #[test]
+    fn business_days_between() {
+        let mon = ymd(2020, 12, 7, UTC);
+        let next_mon = mon.add_days(7);
+        let mut holidays = HashSet::new();
+        assert_eq!(Date::business_days_between(mon, next_mon, &holidays), 5);
+        assert_eq!(Date::business_days_between(mon, next_mon.add_days(1), &holidays), 6);
+        assert_eq!(Date::business_days_between(mon, mon, &holidays), 0);
+
+        holidays.insert(mon.add_days(2));
+        // Outside the range, or on a weekend.
+        holidays.insert(next_mon);
+        holidays.insert(mon.add_days(5));
+        assert_eq!(Date::business_days_between(mon, next_mon, &holidays), 4);
+        assert_eq!(Date::business_days_between(mon, next_mon.add_days(1), &holidays), 4);
+        assert_eq!(Date::business_days_between(mon.add_days(3), next_mon, &holidays), 2);
+    }
+
+    #[test]
+    fn quarters() {
+        for d in DateIter::day(ymd(2020, 4, 1, UTC), ymd(2020, 7, 1, UTC)) {
+            assert_eq!(d.quarter(), 2);
+            assert_eq!(d.quarter_start(), ymd(2020, 4, 1, UTC));
+            assert_eq!(d.quarter_end(), ymd(2020, 6, 30, UTC));
+        }
+        assert_eq!(ymd(2020, 11, 15, UTC).quarter_start(), ymd(2020, 10, 1, UTC));
+        assert_eq!(ymd(2020, 11, 15, UTC).quarter_end(), ymd(2020, 12, 31, UTC));
+        assert_eq!(ymd(2020, 2, 29, UTC).quarter_end(), ymd(2020, 3, 31, UTC));
+        assert_eq!(ymd(2020, 9, 30, UTC).quarter_end(), ymd(2020, 9, 30, UTC));
+
+        assert_eq!(ymd(2020, 5, 31, UTC).with_quarter(1), ymd(2020, 2, 29, UTC));
+        assert_eq!(ymd(2020, 5, 31, UTC).with_quarter(4), ymd(2020, 11, 30, UTC));
+        assert_eq!(ymd(2020, 1, 15, UTC).with_quarter(3), ymd(2020, 7, 15, UTC));
+    }
+
+    #[test]
+    fn last_day_of_month() {
+        assert_eq!(ymd(2020, 2, 10, UTC).last_day_of_month(), ymd(2020, 2, 29, UTC));
+        assert_eq!(ymd(2021, 2, 10, UTC).last_day_of_month(), ymd(2021, 2, 28, UTC));
+        assert_eq!(ymd(2020, 4, 1, UTC).last_day_of_month(), ymd(2020, 4, 30, UTC));
+        assert_eq!(ymd(2020, 12, 31, UTC).last_day_of_month(), ymd(2020, 12, 31, UTC));
+        assert_eq!(ymd(2020, 1, 31, UTC).add_months(1), ymd(2020, 2, 29, UTC));
+    }
+
+    #[test]
+    fn serialization() -> Result<()> {
+        let d = ymd(2020, 1, 30, Sydney);
+        let se = serde_json::to_string(&d)?;
+        assert_eq!(se, "\"2020-01-30 Australia/Sydney\"");
+        let de: Date = serde_json::from_str(&se)?;
+        assert_eq!(de, d);
+        assert_eq!(de.tz(), Sydney);
+
+        let de: Date = serde_json::from_str(&serde_json::to_string(&ymd(2020, 2, 29, UTC))?)?;
+        assert_eq!(de, ymd(2020, 2, 29, UTC));
+        assert!(serde_json::from_str::<Date>("\"2020-01-30\"").is_err());
+        assert!(serde_json::from_str::<Date>("\"2020-01-30 Nowhere/Special\"").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn succ_pred() {
+        let d = ymd(2020, 2, 28, Sydney);
+        assert_eq!(d.succ(), Some(ymd(2020, 2, 29, Sydney)));
+        assert_eq!(d.succ().unwrap().succ(), Some(ymd(2020, 3, 1, Sydney)));
+        assert_eq!(d.pred(), Some(ymd(2020, 2, 27, Sydney)));
+        assert_eq!(ymd(2020, 1, 1, Sydney).pred(), Some(ymd(2019, 12, 31, Sydney)));
+
+        assert_eq!(Date::new(NaiveDate::MAX, Sydney).succ(), None);
+        assert_eq!(Date::new(NaiveDate::MIN, Sydney).pred(), None);
+        assert!(Date::new(NaiveDate::MAX, Sydney).pred().is_some());
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt;
 use std::fmt::Write;
-use std::iter::once;
+use std::iter::{once, Sum};
 use std::str::FromStr;
 
 use auto_ops::{impl_op_ex, impl_op_ex_commutative};
@@ -9,7 +9,7 @@ use eyre::{eyre, Result};
 use num_traits::ToPrimitive;
 use rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformFloat, UniformSampler};
 use rand::prelude::*;
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 use rust_decimal_macros::dec;
 use serde::de::{self, Visitor};
 use serde::{ser, Deserialize, Serialize};
@@ -35,6 +35,10 @@ impl Duration {
     pub const HOUR: Duration = Duration::new(dec!(3600));
     pub const DAY: Duration = Duration::new(dec!(86400));
     pub const WEEK: Duration = Duration::new(dec!(604800));
+    /// Largest and smallest representable durations, e.g. for initializing
+    /// reductions. Use the checked operations for arithmetic near these.
+    pub const MAX_VALUE: Duration = Duration::new(Decimal::MAX);
+    pub const MIN_VALUE: Duration = Duration::new(Decimal::MIN);
 
     pub const BASES: &[(&'static str, Duration)] = &[
         ("w", Duration::WEEK),
@@ -50,6 +54,23 @@ impl Duration {
         ("as", Duration::ASEC),
     ];
 
+    /// Multiples of each unit used by |nice_ceil|, finest unit first. Sub-second
+    /// units go up to 500 so each one joins onto the next, seconds and minutes
+    /// follow the clock face, and hours divide a day.
+    const NICE_LADDER: &'static [(Duration, &'static [i64])] = &[
+        (Duration::ASEC, &[1, 2, 5, 10, 20, 50, 100, 200, 500]),
+        (Duration::FSEC, &[1, 2, 5, 10, 20, 50, 100, 200, 500]),
+        (Duration::PSEC, &[1, 2, 5, 10, 20, 50, 100, 200, 500]),
+        (Duration::NSEC, &[1, 2, 5, 10, 20, 50, 100, 200, 500]),
+        (Duration::USEC, &[1, 2, 5, 10, 20, 50, 100, 200, 500]),
+        (Duration::MSEC, &[1, 2, 5, 10, 20, 50, 100, 200, 500]),
+        (Duration::SEC, &[1, 2, 5, 10, 15, 30]),
+        (Duration::MIN, &[1, 2, 5, 10, 15, 30]),
+        (Duration::HOUR, &[1, 2, 3, 6, 12]),
+        (Duration::DAY, &[1, 2, 3, 5]),
+        (Duration::WEEK, &[1, 2, 5, 10]),
+    ];
+
     pub const fn new(secs: Decimal) -> Self {
         Self { secs }
     }
@@ -84,28 +105,97 @@ impl Duration {
         self.human_bases(Duration::BASES)
     }
 
+    /// Like |human|, but positive durations get a leading '+', e.g. "+1h".
+    /// Zero is written without a sign.
+    pub fn human_signed(&self) -> Result<String> {
+        let human = self.human()?;
+        Ok(if *self > Duration::zero() { format!("+{human}") } else { human })
+    }
+
+    /// Negative durations are written with a single leading '-', e.g. "-1s1ms".
     pub fn human_bases(&self, bases: &[(&str, Duration)]) -> Result<String> {
-        let mut rem = *self;
+        if self.is_zero() {
+            // Prefer "0s" so zero still parses back with |from_human|.
+            let base = bases.iter().find(|v| v.1 == Duration::SEC).or_else(|| bases.last());
+            return base.map(|v| format!("0{}", v.0)).ok_or_else(|| eyre!("no bases"));
+        }
+
         let mut human = String::new();
+        if self.secs.is_sign_negative() {
+            human.push('-');
+        }
+        for (div, s) in self.decompose(bases)? {
+            let _ = write!(human, "{div}{s}");
+        }
+        Ok(human)
+    }
+
+    /// Splits this duration into its non-zero (value, unit) components, the
+    /// same ones |human| writes. The sign is carried on the first component,
+    /// and a zero duration has no components.
+    pub fn components(&self) -> Result<Vec<(i64, &'static str)>> {
+        let mut comps = self
+            .decompose(Duration::BASES)?
+            .into_iter()
+            .map(|(div, s)| div.to_i64().map(|v| (v, s)).ok_or_else(|| eyre!("overflow")))
+            .collect::<Result<Vec<_>>>()?;
+        if self.secs.is_sign_negative() {
+            if let Some(first) = comps.first_mut() {
+                first.0 = -first.0;
+            }
+        }
+        Ok(comps)
+    }
+
+    fn decompose<'a>(&self, bases: &[(&'a str, Duration)]) -> Result<Vec<(Decimal, &'a str)>> {
+        let mut rem = self.abs();
+        let mut comps = Vec::new();
         for &(s, dur) in bases {
             let div = (rem / dur).trunc();
-            rem -= dur * div;
+            let sub = dur.secs.checked_mul(div).map(Duration::new);
+            rem = sub.and_then(|sub| rem.checked_sub(sub)).ok_or_else(|| eyre!("overflow"))?;
             if !div.is_zero() {
-                let _ = write!(human, "{div}{s}");
+                comps.push((div, s));
             }
         }
         // Some sub-attosecond duration...
         if rem.is_zero() {
-            Ok(human)
+            Ok(comps)
         } else {
             Err(eyre!("remainder is not zero"))
         }
     }
 
+    /// Like |human|, but writes at most |max_units| units starting from the
+    /// largest non-zero one, rounding off the remainder. e.g. 1h30m45s with
+    /// two units is "1h31m".
+    pub fn human_rounded(&self, max_units: usize) -> Result<String> {
+        if max_units == 0 {
+            return Err(eyre!("max_units must be positive"));
+        }
+        let abs = self.abs();
+        let Some(first) = Duration::BASES.iter().position(|&(_, dur)| abs >= dur) else {
+            return self.human();
+        };
+        let last = first.saturating_add(max_units - 1).min(Duration::BASES.len() - 1);
+        let unit = Duration::BASES[last].1;
+        let n = abs
+            .secs
+            .checked_div(unit.secs)
+            .ok_or_else(|| eyre!("overflow"))?
+            .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+        let rounded = Duration::new(n.checked_mul(unit.secs).ok_or_else(|| eyre!("overflow"))?);
+        let rounded = if self.secs.is_sign_negative() { -rounded } else { rounded };
+        rounded.human_bases(&Duration::BASES[..=last])
+    }
+
+    /// Parses a duration like "1h30m" or "-5s". Whitespace is allowed between
+    /// components, e.g. "1h 30m", but not between a number and its unit.
     pub fn from_human(s: &str) -> Result<Duration> {
         let mut dur = Duration::zero();
+        let (neg, s) = s.strip_prefix('-').map_or((false, s), |s| (true, s));
 
-        // First character must be a digit:
+        // First character (after any sign) must be a digit:
         if s.is_empty() {
             return Err(eyre!("empty duration"));
         }
@@ -116,8 +206,14 @@ impl Duration {
         let mut cur_number = 0;
         let mut cur_ident = String::new();
         let mut is_digit = true;
+        let mut after_space = false;
         for c in s.chars().chain(once('0')) {
-            if let Some(digit) = c.to_digit(10) {
+            if c.is_whitespace() {
+                if is_digit {
+                    return Err(eyre!("whitespace between number and unit"));
+                }
+                after_space = true;
+            } else if let Some(digit) = c.to_digit(10) {
                 if !is_digit {
                     let base = Duration::BASES
                         .iter()
@@ -129,13 +225,88 @@ impl Duration {
                 }
                 cur_number = cur_number * 10 + digit as i64;
                 is_digit = true;
+                after_space = false;
             } else {
+                if after_space {
+                    return Err(eyre!("whitespace within unit"));
+                }
                 cur_ident.push(c);
                 is_digit = false;
             }
         }
 
-        Ok(dur)
+        Ok(if neg { -dur } else { dur })
+    }
+
+    /// Period of a frequency of |hz| cycles per second.
+    pub fn from_hz(hz: Decimal) -> Result<Self> {
+        if hz <= Decimal::ZERO {
+            return Err(eyre!("frequency {} must be positive", hz));
+        }
+        Ok(Self::new(Decimal::ONE / hz))
+    }
+
+    /// Frequency in cycles per second of a period of this duration.
+    pub fn hz(&self) -> Result<Decimal> {
+        if *self <= Duration::zero() {
+            return Err(eyre!("period {} must be positive", self));
+        }
+        Ok(Decimal::ONE / self.secs)
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::new(self.secs.abs())
+    }
+
+    /// Returns None on overflow.
+    #[must_use]
+    pub fn checked_add(&self, other: Duration) -> Option<Self> {
+        self.secs.checked_add(other.secs).map(Self::new)
+    }
+
+    /// Returns None on overflow.
+    #[must_use]
+    pub fn checked_sub(&self, other: Duration) -> Option<Self> {
+        self.secs.checked_sub(other.secs).map(Self::new)
+    }
+
+    /// Mean of |durations|, or None if there are none.
+    #[must_use]
+    pub fn mean(durations: &[Duration]) -> Option<Self> {
+        if durations.is_empty() {
+            None
+        } else {
+            Some(durations.iter().sum::<Duration>() / durations.len())
+        }
+    }
+
+    /// Rounds the magnitude up to the next "nice" duration, keeping the sign.
+    /// Each unit has its own ladder of multiples, see |NICE_LADDER|: e.g. 7m
+    /// becomes 10m, 11m becomes 15m and 4h becomes 6h. Anything over 10w
+    /// rounds up to a whole number of weeks.
+    pub fn nice_ceil(&self) -> Duration {
+        if self.is_zero() {
+            return *self;
+        }
+        let abs = self.abs();
+        let nice = Duration::NICE_LADDER
+            .iter()
+            .flat_map(|&(base, mults)| mults.iter().map(move |&m| m * base))
+            .filter(|&d| d >= abs)
+            .min()
+            .unwrap_or_else(|| (abs / Duration::WEEK).ceil() * Duration::WEEK);
+        if self.secs.is_sign_negative() {
+            -nice
+        } else {
+            nice
+        }
+    }
+
+    /// Returns true if this duration is a whole multiple of |base|. A zero
+    /// |base| is never divisible into anything.
+    #[must_use]
+    pub fn is_multiple_of(&self, base: Duration) -> bool {
+        !base.is_zero() && (self.secs % base.secs).is_zero()
     }
 }
 
@@ -153,6 +324,8 @@ impl_op_ex!(-= |a: &mut Duration, b: &Duration| { a.secs -= b.secs });
 
 impl_op_ex!(/ |a: &Duration, b: &Duration| -> Decimal { a.secs / b.secs });
 
+impl_op_ex!(-|a: &Duration| -> Duration { Duration::new(-a.secs) });
+
 macro_rules! duration_ops {
     ($t:ty) => {
         impl_op_ex_commutative!(* |a: &Duration, b: &$t| -> Duration { Duration::new(a.secs * Decimal::try_from(*b).unwrap()) });
@@ -164,9 +337,61 @@ macro_rules! duration_ops {
     };
 }
 
+duration_ops!(i32);
 duration_ops!(i64);
+duration_ops!(u32);
+duration_ops!(u64);
+duration_ops!(usize);
 duration_ops!(Decimal);
 
+impl Sum for Duration {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Duration::zero(), |acc, d| acc + d)
+    }
+}
+
+impl<'a> Sum<&'a Duration> for Duration {
+    fn sum<I: Iterator<Item = &'a Duration>>(iter: I) -> Self {
+        iter.fold(Duration::zero(), |acc, d| acc + d)
+    }
+}
+
+/// Converts from seconds. Errors on NaN and infinite values.
+impl TryFrom<f64> for Duration {
+    type Error = eyre::Report;
+
+    fn try_from(secs: f64) -> Result<Self> {
+        if !secs.is_finite() {
+            return Err(eyre!("duration must be finite, got {}", secs));
+        }
+        Ok(Self::new(Decimal::try_from(secs)?))
+    }
+}
+
+impl From<std::time::Duration> for Duration {
+    fn from(d: std::time::Duration) -> Self {
+        Self::new(Decimal::from(d.as_secs()) + Decimal::new(d.subsec_nanos() as i64, 9))
+    }
+}
+
+impl_op_ex!(+ |a: &Duration, b: &std::time::Duration| -> Duration { a + Duration::from(*b) });
+impl_op_ex!(+= |a: &mut Duration, b: &std::time::Duration| { *a += Duration::from(*b) });
+
+impl_op_ex!(-|a: &Duration, b: &std::time::Duration| -> Duration { a - Duration::from(*b) });
+impl_op_ex!(-= |a: &mut Duration, b: &std::time::Duration| { *a -= Duration::from(*b) });
+
+impl PartialEq<std::time::Duration> for Duration {
+    fn eq(&self, other: &std::time::Duration) -> bool {
+        *self == Duration::from(*other)
+    }
+}
+
+impl PartialEq<Duration> for std::time::Duration {
+    fn eq(&self, other: &Duration) -> bool {
+        other == self
+    }
+}
+
 impl ToPrimitive for Duration {
     fn to_i64(&self) -> Option<i64> {
         self.secs().to_i64()
@@ -291,6 +516,66 @@ mod tests {
         assert_eq!("5m", (5 * Duration::MIN).human()?);
         assert_eq!("15m", (15 * Duration::MIN).human()?);
         assert_eq!("15m7s", (15 * Duration::MIN + 7 * Duration::SEC).human()?);
+        assert_eq!("0s", Duration::zero().human()?);
+        assert_eq!("-1as", (-Duration::ASEC).human()?);
+        assert_eq!("-1s1ms", (-(Duration::SEC + Duration::MSEC)).human()?);
+        Ok(())
+    }
+
+    #[test]
+    fn human_signed() -> Result<()> {
+        assert_eq!("+1h", Duration::HOUR.human_signed()?);
+        assert_eq!("-1h", (-Duration::HOUR).human_signed()?);
+        assert_eq!("+1m30s", (Duration::MIN + 30 * Duration::SEC).human_signed()?);
+        assert_eq!("0s", Duration::zero().human_signed()?);
+        assert_eq!("0s", (-Duration::zero()).human_signed()?);
+        Ok(())
+    }
+
+    #[test]
+    fn hz() -> Result<()> {
+        assert_eq!(Duration::from_hz(dec!(2))?, 500 * Duration::MSEC);
+        assert_eq!(Duration::from_hz(dec!(0.5))?, 2 * Duration::SEC);
+        assert_eq!((500 * Duration::MSEC).hz()?, dec!(2));
+        assert_eq!(Duration::from_hz(dec!(1000))?.hz()?, dec!(1000));
+        assert!(Duration::from_hz(dec!(0)).is_err());
+        assert!(Duration::from_hz(dec!(-1)).is_err());
+        assert!(Duration::zero().hz().is_err());
+        assert!((-Duration::SEC).hz().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn components() -> Result<()> {
+        let d = Duration::HOUR + 30 * Duration::MIN + 45 * Duration::SEC;
+        assert_eq!(d.components()?, [(1, "h"), (30, "m"), (45, "s")]);
+        assert_eq!((-d).components()?, [(-1, "h"), (30, "m"), (45, "s")]);
+        assert_eq!((Duration::WEEK + Duration::MSEC).components()?, [(1, "w"), (1, "ms")]);
+        assert!(Duration::zero().components()?.is_empty());
+        assert!(Duration::new(Decimal::new(1, 26)).components().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn human_rounded() -> Result<()> {
+        let d = Duration::HOUR + 30 * Duration::MIN + 45 * Duration::SEC;
+        assert_eq!("1h31m", d.human_rounded(2)?);
+        assert_eq!("2h", d.human_rounded(1)?);
+        assert_eq!("1h30m45s", d.human_rounded(3)?);
+        assert_eq!("1h30m45s", d.human_rounded(10)?);
+        assert_eq!("1h30m45s", d.human_rounded(usize::MAX)?);
+        assert_eq!("-1h31m", (-d).human_rounded(2)?);
+        // Rounding up carries across unit boundaries.
+        assert_eq!("1h", (59 * Duration::MIN + 45 * Duration::SEC).human_rounded(1)?);
+        assert_eq!("1d", (Duration::DAY + Duration::SEC).human_rounded(2)?);
+        // Exact when the remainder is zero.
+        assert_eq!("1h30m", (Duration::HOUR + 30 * Duration::MIN).human_rounded(2)?);
+        assert_eq!("1h", Duration::HOUR.human_rounded(2)?);
+        assert_eq!("0s", Duration::zero().human_rounded(2)?);
+        assert!(d.human_rounded(0).is_err());
+        // Too many units to represent at the finest one.
+        assert!(Duration::MAX_VALUE.human_rounded(usize::MAX).is_err());
+        assert!(Duration::MIN_VALUE.human_rounded(usize::MAX).is_err());
         Ok(())
     }
 
@@ -319,9 +604,168 @@ mod tests {
         assert_eq!(Duration::from_human("5m")?, (5 * Duration::MIN));
         assert_eq!(Duration::from_human("15m")?, (15 * Duration::MIN));
         assert_eq!(Duration::from_human("15m7s")?, (15 * Duration::MIN + 7 * Duration::SEC));
+        assert_eq!(Duration::from_human("0s")?, Duration::zero());
+        assert_eq!(Duration::from_human("-0s")?, Duration::zero());
+        assert_eq!(Duration::from_human("-1as")?, -Duration::ASEC);
+        assert_eq!(Duration::from_human("-1s1ms")?, -(Duration::SEC + Duration::MSEC));
+        assert!(Duration::from_human("-").is_err());
+        assert!(Duration::from_human("--1s").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn from_human_whitespace() -> Result<()> {
+        let expected = Duration::HOUR + 30 * Duration::MIN;
+        assert_eq!(Duration::from_human("1h30m")?, expected);
+        assert_eq!(Duration::from_human("1h 30m")?, expected);
+        assert_eq!(Duration::from_human("1h \t 30m ")?, expected);
+        assert_eq!(Duration::from_human("-1h 30m")?, -expected);
+        assert_eq!("1h 30m".parse::<Duration>()?, expected);
+
+        // Whitespace can only separate components.
+        assert!(Duration::from_human("1 h").is_err());
+        assert!(Duration::from_human("1h 3 0m").is_err());
+        assert!(Duration::from_human("1m s").is_err());
+        assert!(Duration::from_human(" 1h").is_err());
+        assert!(Duration::from_human("- 1h").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn human_round_trip() -> Result<()> {
+        let mut r = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let mut d = Duration::zero();
+            for &(_, base) in Duration::BASES {
+                d += r.gen_range(0..10i64) * base;
+            }
+            if r.gen_bool(0.5) {
+                d = -d;
+            }
+            assert_eq!(Duration::from_human(&d.human()?)?, d);
+        }
         Ok(())
     }
 
+    #[test]
+    fn is_multiple_of() {
+        assert!(Duration::HOUR.is_multiple_of(Duration::MIN));
+        assert!(Duration::zero().is_multiple_of(Duration::MIN));
+        assert!((-Duration::HOUR).is_multiple_of(Duration::MIN));
+        assert!(!(90 * Duration::SEC).is_multiple_of(60 * Duration::SEC));
+        assert!(!Duration::HOUR.is_multiple_of(Duration::zero()));
+    }
+
+    #[test]
+    fn try_from_f64() -> Result<()> {
+        assert_eq!(Duration::try_from(1.5f64)?, Duration::new(dec!(1.5)));
+        assert_eq!(Duration::try_from(-60.0f64)?, -Duration::MIN);
+        assert!(Duration::try_from(f64::INFINITY).is_err());
+        assert!(Duration::try_from(f64::NEG_INFINITY).is_err());
+        assert!(Duration::try_from(f64::NAN).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn std_duration_ops() {
+        assert_eq!(Duration::SEC, std::time::Duration::from_secs(1));
+        assert_eq!(std::time::Duration::from_millis(1500), Duration::new(dec!(1.5)));
+        assert_ne!(Duration::SEC, std::time::Duration::from_secs(2));
+        assert_ne!(-Duration::SEC, std::time::Duration::from_secs(1));
+        assert_eq!(Duration::MIN + std::time::Duration::from_secs(30), Duration::new(dec!(90)));
+        assert_eq!(
+            Duration::MIN - std::time::Duration::from_nanos(1),
+            Duration::MIN - Duration::NSEC
+        );
+
+        let mut d = Duration::HOUR;
+        d += std::time::Duration::from_secs(60);
+        assert_eq!(d, Duration::HOUR + Duration::MIN);
+        d -= std::time::Duration::from_secs(3600);
+        assert_eq!(d, Duration::MIN);
+        assert_eq!(Duration::from(d.to_chrono()), d);
+    }
+
+    #[test]
+    fn sum() {
+        let durs = [Duration::SEC, Duration::MIN];
+        assert_eq!(durs.iter().copied().sum::<Duration>(), 61 * Duration::SEC);
+        assert_eq!(durs.iter().sum::<Duration>(), 61 * Duration::SEC);
+        assert_eq!([Duration::SEC; 0].iter().sum::<Duration>(), Duration::zero());
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(Duration::mean(&[Duration::SEC, 3 * Duration::SEC]), Some(2 * Duration::SEC));
+        assert_eq!(
+            Duration::mean(&[Duration::SEC, Duration::SEC, Duration::zero()]),
+            Some(Duration::new(dec!(2) / dec!(3)))
+        );
+        assert_eq!(Duration::mean(&[-Duration::MIN]), Some(-Duration::MIN));
+        assert_eq!(Duration::mean(&[]), None);
+    }
+
+    #[test]
+    fn nice_ceil() {
+        assert_eq!(Duration::zero().nice_ceil(), Duration::zero());
+        assert_eq!((3 * Duration::NSEC).nice_ceil(), 5 * Duration::NSEC);
+        assert_eq!((11 * Duration::NSEC).nice_ceil(), 20 * Duration::NSEC);
+        assert_eq!((501 * Duration::NSEC).nice_ceil(), Duration::USEC);
+        assert_eq!((11 * Duration::USEC).nice_ceil(), 20 * Duration::USEC);
+        assert_eq!((3 * Duration::MSEC).nice_ceil(), 5 * Duration::MSEC);
+        assert_eq!((11 * Duration::MSEC).nice_ceil(), 20 * Duration::MSEC);
+        assert_eq!((150 * Duration::MSEC).nice_ceil(), 200 * Duration::MSEC);
+        assert_eq!((501 * Duration::MSEC).nice_ceil(), Duration::SEC);
+        assert_eq!(Duration::SEC.nice_ceil(), Duration::SEC);
+        assert_eq!((7 * Duration::SEC).nice_ceil(), 10 * Duration::SEC);
+        assert_eq!((11 * Duration::SEC).nice_ceil(), 15 * Duration::SEC);
+        assert_eq!((21 * Duration::SEC).nice_ceil(), 30 * Duration::SEC);
+        assert_eq!((31 * Duration::SEC).nice_ceil(), Duration::MIN);
+        assert_eq!((7 * Duration::MIN).nice_ceil(), 10 * Duration::MIN);
+        assert_eq!((11 * Duration::MIN).nice_ceil(), 15 * Duration::MIN);
+        assert_eq!((45 * Duration::MIN).nice_ceil(), Duration::HOUR);
+        assert_eq!((3 * Duration::HOUR).nice_ceil(), 3 * Duration::HOUR);
+        assert_eq!((4 * Duration::HOUR).nice_ceil(), 6 * Duration::HOUR);
+        assert_eq!((11 * Duration::HOUR).nice_ceil(), 12 * Duration::HOUR);
+        assert_eq!((13 * Duration::HOUR).nice_ceil(), Duration::DAY);
+        assert_eq!((36 * Duration::HOUR).nice_ceil(), 2 * Duration::DAY);
+        assert_eq!((4 * Duration::DAY).nice_ceil(), 5 * Duration::DAY);
+        assert_eq!((6 * Duration::DAY).nice_ceil(), Duration::WEEK);
+        assert_eq!((11 * Duration::DAY).nice_ceil(), 2 * Duration::WEEK);
+        assert_eq!((3 * Duration::WEEK).nice_ceil(), 5 * Duration::WEEK);
+        assert_eq!((11 * Duration::WEEK + Duration::SEC).nice_ceil(), 12 * Duration::WEEK);
+        assert_eq!((-7 * Duration::MIN).nice_ceil(), -10 * Duration::MIN);
+    }
+
+    #[test]
+    fn integer_ops() {
+        assert_eq!(Duration::SEC * 3u32, 3 * Duration::SEC);
+        assert_eq!(3usize * Duration::SEC, 3 * Duration::SEC);
+        assert_eq!(Duration::MIN / 60u64, Duration::SEC);
+        assert_eq!(-2i32 * Duration::HOUR, -(2 * Duration::HOUR));
+
+        let mut d = Duration::SEC;
+        d *= 4usize;
+        d /= 2u32;
+        assert_eq!(d, 2 * Duration::SEC);
+    }
+
+    #[test]
+    fn extreme_values() {
+        assert!(Duration::MAX_VALUE > Duration::WEEK);
+        assert!(Duration::MIN_VALUE < Duration::zero());
+        assert_eq!(Duration::MAX_VALUE.checked_add(Duration::SEC), None);
+        assert_eq!(Duration::MIN_VALUE.checked_sub(Duration::SEC), None);
+        assert_eq!(
+            Duration::MAX_VALUE.checked_sub(Duration::SEC),
+            Some(Duration::new(Decimal::MAX - dec!(1)))
+        );
+        assert_eq!(Duration::SEC.checked_add(Duration::SEC), Some(2 * Duration::SEC));
+        // Must not panic, though may error.
+        let _ = Duration::MAX_VALUE.human();
+        let _ = Duration::MIN_VALUE.human();
+    }
+
     #[test]
     fn serialization() -> Result<()> {
         let dur = Duration::DAY;
@@ -1,8 +1,12 @@
+use std::iter::successors;
+
 use derive_more::Display;
 use serde::{Deserialize, Serialize};
 
 use crate::date::Date;
+use crate::duration::Duration;
 use crate::op::{DateOp, TimeOp};
+use crate::span::any::SpanAny;
 use crate::time::Time;
 
 #[must_use]
@@ -67,3 +71,41 @@ impl Iterator for DateIter {
         }
     }
 }
+
+impl SpanAny<Time> {
+    /// Iterates over times in the span starting from its closed start, in
+    /// increments of |step|. Empty if the start is unbounded.
+    pub fn iter_step(&self, step: Duration) -> impl Iterator<Item = Time> {
+        assert!(step > Duration::zero(), "step {step} must be positive");
+        let span = *self;
+        successors(span.lower_value(), move |t| t.checked_add(step))
+            .take_while(move |t| span.contains(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::US::Eastern;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::time::ymdhms;
+
+    #[test]
+    fn span_iter_step() {
+        let st = ymdhms(2020, 1, 1, 9, 0, 0, Eastern);
+        let en = ymdhms(2020, 1, 1, 12, 0, 0, Eastern);
+
+        let times: Vec<_> = SpanAny::exc(st, en).iter_step(Duration::HOUR).collect();
+        assert_eq!(
+            times,
+            [st, ymdhms(2020, 1, 1, 10, 0, 0, Eastern), ymdhms(2020, 1, 1, 11, 0, 0, Eastern)]
+        );
+        assert!(times.iter().all(|t| t.tz() == Eastern));
+
+        assert_eq!(SpanAny::inc(st, en).iter_step(Duration::HOUR).count(), 4);
+        assert_eq!(SpanAny::inc_unb(st).iter_step(Duration::HOUR).take(10).count(), 10);
+        assert_eq!(SpanAny::unb_inc(en).iter_step(Duration::HOUR).count(), 0);
+        assert_eq!(SpanAny::exc(en, st).iter_step(Duration::HOUR).count(), 0);
+    }
+}
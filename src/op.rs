@@ -3,6 +3,8 @@ use num_traits::FromPrimitive;
 use serde::{Deserialize, Serialize};
 
 use crate::date::Date;
+use crate::seq::scalar_series::TimeSeries;
+use crate::seq::series::Series;
 use crate::span::exc::SpanExc;
 use crate::time::Time;
 
@@ -50,6 +52,12 @@ pub enum TOp {
     SetMillis,
     SetMicros,
     SetNanos,
+    AddQuarters = 37,
+    // Moves back to the first day of the current quarter.
+    FindQuarterStart = 38,
+    SetQuarter = 39,
+    // Sets the day counting back from the end of the month, 0 being the last day.
+    SetDayFromEnd = 40,
 }
 
 #[must_use]
@@ -152,6 +160,14 @@ impl TimeOp {
         Self::add_months(1)
     }
 
+    pub const fn add_quarters(n: i64) -> Self {
+        Self::new(TOp::AddQuarters, n)
+    }
+
+    pub const fn quarterly() -> Self {
+        Self::add_quarters(1)
+    }
+
     pub const fn weekly() -> Self {
         Self::add_days(7)
     }
@@ -176,6 +192,18 @@ impl TimeOp {
         Self::new(TOp::SetDay, n)
     }
 
+    pub const fn find_quarter_start() -> Self {
+        Self::new(TOp::FindQuarterStart, 0)
+    }
+
+    pub const fn set_quarter(n: i64) -> Self {
+        Self::new(TOp::SetQuarter, n)
+    }
+
+    pub const fn set_day_from_end(n: i64) -> Self {
+        Self::new(TOp::SetDayFromEnd, n)
+    }
+
     pub const fn nop() -> Self {
         Self::new(TOp::Nop, 0)
     }
@@ -262,6 +290,18 @@ impl TimeOp {
             )),
         }
     }
+
+    /// Applies each of |ops| independently to |start|, tagging each resulting
+    /// time with the op that produced it. Useful for previewing schedules.
+    pub fn apply_all(ops: &[TimeOp], start: impl Into<Time>) -> TimeSeries<TimeOp> {
+        let start = start.into();
+        let mut data: Vec<_> = ops.iter().map(|op| (op.apply(start), *op)).collect();
+        // Ops needn't be monotonic in their results; stable sort keeps ties in order.
+        data.sort_by_key(|v| v.0);
+        let mut series = TimeSeries::new();
+        series.inner_mut().data_mut().extend(data);
+        series
+    }
 }
 
 #[must_use]
@@ -296,6 +336,12 @@ pub enum DOp {
     SetMonth = 22,
     SetDay = 23,
     Nop = 24,
+    AddQuarters = 37,
+    // Moves back to the first day of the current quarter.
+    FindQuarterStart = 38,
+    SetQuarter = 39,
+    // Sets the day counting back from the end of the month, 0 being the last day.
+    SetDayFromEnd = 40,
 }
 
 #[must_use]
@@ -398,6 +444,14 @@ impl DateOp {
         Self::add_months(1)
     }
 
+    pub const fn add_quarters(n: i64) -> Self {
+        Self::new(DOp::AddQuarters, n)
+    }
+
+    pub const fn quarterly() -> Self {
+        Self::add_quarters(1)
+    }
+
     pub const fn add_days(n: i64) -> Self {
         Self::new(DOp::AddDays, n)
     }
@@ -418,6 +472,18 @@ impl DateOp {
         Self::new(DOp::SetDay, n)
     }
 
+    pub const fn find_quarter_start() -> Self {
+        Self::new(DOp::FindQuarterStart, 0)
+    }
+
+    pub const fn set_quarter(n: i64) -> Self {
+        Self::new(DOp::SetQuarter, n)
+    }
+
+    pub const fn set_day_from_end(n: i64) -> Self {
+        Self::new(DOp::SetDayFromEnd, n)
+    }
+
     pub const fn nop() -> Self {
         Self::new(DOp::Nop, 0)
     }
@@ -431,6 +497,7 @@ fn apply_dop(d: Date, op: DOp, n: i64) -> Date {
     match op {
         DOp::AddYears => d.add_years(n as i32),
         DOp::AddMonths => d.add_months(n as i32),
+        DOp::AddQuarters => d.add_months(3 * n as i32),
         DOp::AddDays => d.add_days(n as i32),
         DOp::AdvDay => {
             let n = d.with_day(n as u32);
@@ -478,6 +545,9 @@ fn apply_dop(d: Date, op: DOp, n: i64) -> Date {
         DOp::SetYear => d.with_year(n as i32),
         DOp::SetMonth => d.with_month(n as u32),
         DOp::SetDay => d.with_day(n as u32),
+        DOp::SetQuarter => d.with_quarter(n as u32),
+        DOp::SetDayFromEnd => d.last_day_of_month().add_days(-(n as i32)),
+        DOp::FindQuarterStart => d.quarter_start(),
         _ => d,
     }
 }
@@ -494,10 +564,35 @@ impl SpanOp {
         Self { st, en }
     }
 
+    /// Applies |st| and |en| independently to |t|.
     pub fn apply(&self, t: impl Into<Time>) -> SpanExc<Time> {
         let t = t.into();
         SpanExc::new(self.st.apply(t), self.en.apply(t))
     }
+
+    /// Applies |st| to |t|, then |en| to the resulting start time.
+    pub fn apply_chained(&self, t: impl Into<Time>) -> SpanExc<Time> {
+        let st = self.st.apply(t);
+        SpanExc::new(st, self.en.apply(st))
+    }
+}
+
+#[must_use]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Serialize, Deserialize)]
+pub struct DateSpanOp {
+    pub st: DateOp,
+    pub en: DateOp, // Exclusive.
+}
+
+impl DateSpanOp {
+    pub const fn new(st: DateOp, en: DateOp) -> Self {
+        Self { st, en }
+    }
+
+    /// Applies |st| and |en| independently to |d|.
+    pub fn apply(&self, d: Date) -> SpanExc<Date> {
+        SpanExc::new(self.st.apply(d), self.en.apply(d))
+    }
 }
 
 #[cfg(test)]
@@ -690,4 +785,129 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn quarters() -> Result<()> {
+        for tz in &TZ {
+            assert_eq!(
+                TimeOp::add_quarters(1).apply(ymd(2020, 3, 15, tz).time()?),
+                ymd(2020, 6, 15, tz).time()?,
+            );
+            assert_eq!(
+                TimeOp::add_quarters(-1).apply(ymd(2020, 3, 31, tz).time()?),
+                ymd(2019, 12, 31, tz).time()?,
+            );
+            assert_eq!(
+                TimeOp::find_quarter_start().apply(ymd(2020, 5, 15, tz).time()?),
+                ymd(2020, 4, 1, tz).time()?,
+            );
+            assert_eq!(
+                TimeOp::find_quarter_start().apply(ymd(2020, 10, 1, tz).time()?),
+                ymd(2020, 10, 1, tz).time()?,
+            );
+            assert_eq!(DateOp::add_quarters(4).apply(ymd(2020, 2, 29, tz)), ymd(2021, 2, 28, tz));
+            assert_eq!(
+                TimeOp::set_quarter(2).apply(ymd(2020, 11, 30, tz).time()?),
+                ymd(2020, 5, 30, tz).time()?,
+            );
+            assert_eq!(
+                DateOp::find_quarter_start().apply(ymd(2020, 12, 31, tz)),
+                ymd(2020, 10, 1, tz)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn set_day_from_end() -> Result<()> {
+        for tz in &TZ {
+            for d in [1, 15, 30] {
+                assert_eq!(
+                    DateOp::set_day_from_end(0).apply(ymd(2020, 4, d, tz)),
+                    ymd(2020, 4, 30, tz)
+                );
+                assert_eq!(
+                    DateOp::set_day_from_end(1).apply(ymd(2020, 4, d, tz)),
+                    ymd(2020, 4, 29, tz)
+                );
+            }
+            assert_eq!(
+                DateOp::set_day_from_end(0).apply(ymd(2020, 2, 3, tz)),
+                ymd(2020, 2, 29, tz)
+            );
+            assert_eq!(
+                DateOp::set_day_from_end(1).apply(ymd(2020, 2, 3, tz)),
+                ymd(2020, 2, 28, tz)
+            );
+            assert_eq!(
+                DateOp::set_day_from_end(0).apply(ymd(2021, 2, 3, tz)),
+                ymd(2021, 2, 28, tz)
+            );
+            assert_eq!(
+                DateOp::set_day_from_end(1).apply(ymd(2021, 2, 3, tz)),
+                ymd(2021, 2, 27, tz)
+            );
+            assert_eq!(
+                TimeOp::set_day_from_end(2).apply(ymd(2020, 12, 1, tz).time()?),
+                ymd(2020, 12, 29, tz).time()?,
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn span_op_chained() -> Result<()> {
+        for tz in &TZ {
+            // From next Monday for a week, applied on a Wednesday.
+            let op = SpanOp::new(TimeOp::find_mon(1), TimeOp::weekly());
+            let t = ymd(2020, 12, 9, tz).time()?;
+            assert_eq!(
+                op.apply(t),
+                SpanExc::new(ymd(2020, 12, 14, tz).time()?, ymd(2020, 12, 16, tz).time()?)
+            );
+            assert_eq!(
+                op.apply_chained(t),
+                SpanExc::new(ymd(2020, 12, 14, tz).time()?, ymd(2020, 12, 21, tz).time()?)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn apply_all() -> Result<()> {
+        for tz in &TZ {
+            let t = ymd(2020, 1, 15, tz).time()?;
+            let ops = [TimeOp::monthly(), TimeOp::daily(), TimeOp::weekly()];
+            let series = TimeOp::apply_all(&ops, t);
+            assert_eq!(
+                series.slice(),
+                &[
+                    (ymd(2020, 1, 16, tz).time()?, TimeOp::daily()),
+                    (ymd(2020, 1, 22, tz).time()?, TimeOp::weekly()),
+                    (ymd(2020, 2, 15, tz).time()?, TimeOp::monthly()),
+                ]
+            );
+            assert!(TimeOp::apply_all(&[], t).is_empty());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn date_span_op() {
+        let tz = US::Eastern;
+        let week = DateSpanOp::new(DateOp::find_mon(-1), DateOp::find_sat(1));
+        // 2020-12-09 is a Wednesday.
+        assert_eq!(
+            week.apply(ymd(2020, 12, 9, tz)),
+            SpanExc::new(ymd(2020, 12, 7, tz), ymd(2020, 12, 12, tz))
+        );
+        assert_eq!(
+            week.apply(ymd(2020, 12, 7, tz)),
+            SpanExc::new(ymd(2020, 12, 7, tz), ymd(2020, 12, 12, tz))
+        );
+        assert_eq!(
+            week.apply(ymd(2020, 12, 12, tz)),
+            SpanExc::new(ymd(2020, 12, 7, tz), ymd(2020, 12, 12, tz))
+        );
+    }
 }
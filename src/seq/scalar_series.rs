@@ -1,9 +1,15 @@
+use std::collections::VecDeque;
+
 use eyre::Result;
 
+use crate::duration::Duration;
+use crate::op::{SpanOp, TimeOp};
 use crate::seq::inner::SeriesInner;
 use crate::seq::series::Series;
+use crate::seq::span_series::SpanExcSeries;
 use crate::series_ops;
 use crate::span::any::SpanAny;
+use crate::span::exc::SpanExc;
 use crate::time::Time;
 
 pub type TimeSeries<Y> = ScalarSeries<Time, Y>;
@@ -19,6 +25,88 @@ impl<X: PartialOrd + Copy + std::fmt::Display, Y: Clone> ScalarSeries<X, Y> {
     pub fn new() -> Self {
         Self { inner: SeriesInner::empty() }
     }
+
+    /// Applies |f| to each x value, keeping the values, and re-sorts the result.
+    pub fn map_x<X2, F>(&self, f: F) -> ScalarSeries<X2, Y>
+    where
+        X2: PartialOrd + Copy + std::fmt::Display,
+        F: FnMut(&X) -> X2,
+    {
+        let mut series = self.map_x_monotonic(f);
+        series.normalize().unwrap();
+        series
+    }
+
+    /// Like |map_x|, but |f| must preserve the order of the x values (e.g. a
+    /// constant shift), so no re-sort is done.
+    pub fn map_x_monotonic<X2, F>(&self, mut f: F) -> ScalarSeries<X2, Y>
+    where
+        X2: PartialOrd + Copy + std::fmt::Display,
+        F: FnMut(&X) -> X2,
+    {
+        let data: Vec<_> = self.iter().map(|(x, y)| (f(x), y.clone())).collect();
+        ScalarSeries { inner: SeriesInner::new(data) }
+    }
+
+    /// Pairs each x with the value |n| points before it, or None if there
+    /// isn't one.
+    pub fn lag(&self, n: usize) -> ScalarSeries<X, Option<Y>> {
+        let data: Vec<_> = self
+            .iter()
+            .enumerate()
+            .map(|(i, (x, _))| (*x, i.checked_sub(n).map(|i| self[i].1.clone())))
+            .collect();
+        ScalarSeries { inner: SeriesInner::new(data) }
+    }
+
+    /// Pairs each x with the value |n| points after it, or None if there
+    /// isn't one.
+    pub fn lead(&self, n: usize) -> ScalarSeries<X, Option<Y>> {
+        let data: Vec<_> = self
+            .iter()
+            .enumerate()
+            .map(|(i, (x, _))| (*x, self.get(i.saturating_add(n)).map(|v| v.1.clone())))
+            .collect();
+        ScalarSeries { inner: SeriesInner::new(data) }
+    }
+
+    /// Returns the first point within |span| whose value satisfies |pred|.
+    #[must_use]
+    pub fn first_in_span_where<F: FnMut(&Y) -> bool>(
+        &self,
+        span: SpanAny<X>,
+        mut pred: F,
+    ) -> Option<&(X, Y)> {
+        self.subseq(span).iter().find(|v| pred(&v.1))
+    }
+
+    /// Returns the last point within |span| whose value satisfies |pred|.
+    #[must_use]
+    pub fn last_in_span_where<F: FnMut(&Y) -> bool>(
+        &self,
+        span: SpanAny<X>,
+        mut pred: F,
+    ) -> Option<&(X, Y)> {
+        self.subseq(span).iter().rev().find(|v| pred(&v.1))
+    }
+
+    /// As-of join: combines each point with the value of the most recent point
+    /// in |other| at or before it, if any.
+    pub fn join_asof<Y2, Z, F>(&self, other: &ScalarSeries<X, Y2>, mut f: F) -> ScalarSeries<X, Z>
+    where
+        Y2: Clone,
+        Z: Clone,
+        F: FnMut(&Y, Option<&Y2>) -> Z,
+    {
+        let data: Vec<_> = self
+            .iter()
+            .map(|(x, y)| {
+                let prev = if other.is_empty() { None } else { other.span_at_or_before(*x) };
+                (*x, f(y, prev.map(|v| &v.1)))
+            })
+            .collect();
+        ScalarSeries { inner: SeriesInner::new(data) }
+    }
 }
 
 impl<X: PartialOrd + Copy + std::fmt::Display, Y: Clone> Series for ScalarSeries<X, Y> {
@@ -65,12 +153,308 @@ impl<X: PartialOrd + Copy + std::fmt::Display, Y: Clone> Series for ScalarSeries
 
 series_ops!(ScalarSeries<X, Y>; X: PartialOrd + Copy + std::fmt::Display, Y: Clone);
 
+/// Summary statistics of the values of a `ScalarSeries`.
+#[must_use]
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
+pub struct SeriesStats<X> {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub sum: f64,
+    pub first_x: X,
+    pub last_x: X,
+}
+
+/// Open, high, low and close values of a bucket of a `ScalarSeries`.
+#[must_use]
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
+pub struct Ohlc {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl Ohlc {
+    pub const fn new(v: f64) -> Self {
+        Self { open: v, high: v, low: v, close: v }
+    }
+
+    pub fn update(&mut self, v: f64) {
+        self.high = self.high.max(v);
+        self.low = self.low.min(v);
+        self.close = v;
+    }
+}
+
+impl<X: PartialOrd + Copy + std::fmt::Display> ScalarSeries<X, f64> {
+    /// Returns None if the series is empty.
+    #[must_use]
+    pub fn stats(&self) -> Option<SeriesStats<X>> {
+        let first_x = self.first()?.0;
+        let last_x = self.last()?.0;
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        let mut sum = 0.0;
+        for &(_, y) in self.iter() {
+            min = min.min(y);
+            max = max.max(y);
+            sum += y;
+        }
+        let count = self.len();
+        Some(SeriesStats { count, min, max, mean: sum / count as f64, sum, first_x, last_x })
+    }
+
+    /// Quantile |q| in [0, 1] of the y values, linearly interpolating between
+    /// order statistics. Returns None if the series is empty.
+    #[must_use]
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        assert!((0.0..=1.0).contains(&q), "quantile {q} must be in [0, 1]");
+        if self.is_empty() {
+            return None;
+        }
+        let mut ys: Vec<f64> = self.ys().copied().collect();
+        ys.sort_by(f64::total_cmp);
+        let pos = q * (ys.len() - 1) as f64;
+        let lo = pos.floor() as usize;
+        let hi = pos.ceil() as usize;
+        Some(ys[lo] + (ys[hi] - ys[lo]) * (pos - lo as f64))
+    }
+
+    /// Pearson correlation with |other| over the points whose x values match
+    /// exactly. Returns None if fewer than two points match or either side
+    /// has zero variance.
+    #[must_use]
+    pub fn correlation(&self, other: &ScalarSeries<X, f64>) -> Option<f64> {
+        let mut pairs = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.len() && j < other.len() {
+            let (a, b) = (&self[i], &other[j]);
+            if a.0 < b.0 {
+                i += 1;
+            } else if b.0 < a.0 {
+                j += 1;
+            } else {
+                pairs.push((a.1, b.1));
+                i += 1;
+                j += 1;
+            }
+        }
+        if pairs.len() < 2 {
+            return None;
+        }
+
+        let n = pairs.len() as f64;
+        let mean_a = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_b = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+        let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+        for &(a, b) in &pairs {
+            cov += (a - mean_a) * (b - mean_b);
+            var_a += (a - mean_a) * (a - mean_a);
+            var_b += (b - mean_b) * (b - mean_b);
+        }
+        if var_a == 0.0 || var_b == 0.0 {
+            return None;
+        }
+        Some(cov / (var_a * var_b).sqrt())
+    }
+}
+
+impl<X: PartialOrd + Copy + std::fmt::Display, Y: Clone> ScalarSeries<X, Option<Y>> {
+    /// Drops the None values, unwrapping the rest.
+    pub fn dropna(&self) -> ScalarSeries<X, Y> {
+        let data: Vec<_> =
+            self.iter().filter_map(|(x, y)| y.as_ref().map(|y| (*x, y.clone()))).collect();
+        ScalarSeries { inner: SeriesInner::new(data) }
+    }
+}
+
+impl<Y: Clone> ScalarSeries<Time, Y> {
+    /// Applies |op| to every time in the series. The result is re-sorted since
+    /// |op| need not be monotonic.
+    pub fn map_x_time_op(&self, op: TimeOp) -> TimeSeries<Y> {
+        self.map_x(|t| op.apply(*t))
+    }
+
+    /// Number of points in the closed trailing window [x - window, x] at each
+    /// point, including any later points with the same x. Unlike
+    /// |rolling_max|, both ends of the window are included.
+    pub fn rolling_count(&self, window: Duration) -> TimeSeries<usize> {
+        assert!(window > Duration::zero(), "window {window} must be positive");
+        let data = self.slice();
+        let res: Vec<_> = data
+            .iter()
+            .map(|&(t, _)| {
+                let st = data.partition_point(|v| v.0 < t - window);
+                let en = data.partition_point(|v| v.0 <= t);
+                (t, en - st)
+            })
+            .collect();
+        TimeSeries { inner: SeriesInner::new(res) }
+    }
+
+    /// Turns each point into the span [x, x + width) with the same value.
+    /// The spans may overlap.
+    pub fn to_span_series(&self, width: Duration) -> SpanExcSeries<Time, Y> {
+        let mut series = SpanExcSeries::new();
+        series
+            .inner_mut()
+            .data_mut()
+            .extend(self.iter().map(|(t, y)| (SpanExc::new(*t, *t + width), y.clone())));
+        series
+    }
+}
+
+impl<Y: PartialOrd + Copy> ScalarSeries<Time, Y> {
+    /// Maximum over the trailing window (x - window, x] at each point.
+    pub fn rolling_max(&self, window: Duration) -> TimeSeries<Y> {
+        self.rolling_extreme(window, |a, b| a >= b)
+    }
+
+    /// Minimum over the trailing window (x - window, x] at each point.
+    pub fn rolling_min(&self, window: Duration) -> TimeSeries<Y> {
+        self.rolling_extreme(window, |a, b| a <= b)
+    }
+
+    /// Amortized O(n) using a monotonic deque of indices. |dominates(a, b)|
+    /// is true if a newer value a means b can never be the extreme again.
+    fn rolling_extreme(&self, window: Duration, dominates: impl Fn(&Y, &Y) -> bool) -> Self {
+        assert!(window > Duration::zero(), "window {window} must be positive");
+        let data = self.slice();
+        let mut deque: VecDeque<usize> = VecDeque::new();
+        let mut res = Vec::with_capacity(data.len());
+        for (idx, &(t, y)) in data.iter().enumerate() {
+            while let Some(&back) = deque.back() {
+                if !dominates(&y, &data[back].1) {
+                    break;
+                }
+                deque.pop_back();
+            }
+            deque.push_back(idx);
+            let st = t - window;
+            while let Some(&front) = deque.front() {
+                if data[front].0 > st {
+                    break;
+                }
+                deque.pop_front();
+            }
+            res.push((t, data[deque[0]].1));
+        }
+        Self { inner: SeriesInner::new(res) }
+    }
+}
+
+impl ScalarSeries<Time, f64> {
+    /// Buckets the series into candles. The first bucket is |op| applied to
+    /// |anchor|, and each following one is |op| applied to the end of the
+    /// previous. Points before the first bucket or in gaps between buckets
+    /// are ignored, and buckets without any points are skipped.
+    pub fn ohlc(&self, op: SpanOp, anchor: Time) -> SpanExcSeries<Time, Ohlc> {
+        let mut series = SpanExcSeries::new();
+        let mut span = op.apply(anchor);
+        let mut candle: Option<Ohlc> = None;
+        for &(t, v) in self.subseq(SpanAny::inc_unb(span.st)) {
+            while t >= span.en {
+                if let Some(candle) = candle.take() {
+                    series.inner_mut().data_mut().push((span, candle));
+                }
+                let next = op.apply(span.en);
+                assert!(next.en > span.en, "span op {op:?} must advance");
+                span = next;
+            }
+            if t < span.st {
+                continue;
+            }
+            match &mut candle {
+                Some(candle) => candle.update(v),
+                None => candle = Some(Ohlc::new(v)),
+            }
+        }
+        if let Some(candle) = candle {
+            series.inner_mut().data_mut().push((span, candle));
+        }
+        series
+    }
+
+    /// Time weighted average over |span|, treating the series as a step
+    /// function where each value holds until the next point. None if no value
+    /// is active at the start of |span| or |span| is empty.
+    #[must_use]
+    pub fn twap(&self, span: SpanExc<Time>) -> Option<f64> {
+        if span.is_empty() || self.is_empty() {
+            return None;
+        }
+        let idx = self.span_at_or_before_idx(span.st)?;
+        let (mut t, mut y) = (span.st, self[idx].1);
+        let mut sum = 0.0;
+        for &(x, v) in &self.slice()[idx + 1..] {
+            if x >= span.en {
+                break;
+            }
+            sum += (x - t).secs_f64() * y;
+            (t, y) = (x, v);
+        }
+        sum += (span.en - t).secs_f64() * y;
+        Some(sum / span.size().secs_f64())
+    }
+
+    /// Trapezoidal area under the series over |span|, in value-seconds,
+    /// linearly interpolating between points. Segments are clipped at the
+    /// ends of |span| and nothing is extrapolated beyond the first or last
+    /// point. None if |span| doesn't overlap any segment.
+    #[must_use]
+    pub fn integrate(&self, span: SpanExc<Time>) -> Option<f64> {
+        if span.is_empty() || self.is_empty() {
+            return None;
+        }
+        let idx = self.span_at_or_before_idx(span.st).unwrap_or(0);
+        let mut area = None;
+        for w in self.slice()[idx..].windows(2) {
+            let ((ta, ya), (tb, yb)) = (w[0], w[1]);
+            if ta >= span.en {
+                break;
+            }
+            let (st, en) = (ta.max(span.st), tb.min(span.en));
+            if st >= en {
+                continue;
+            }
+            let at = |t: Time| ya + (yb - ya) * (t - ta).secs_f64() / (tb - ta).secs_f64();
+            *area.get_or_insert(0.0) += (en - st).secs_f64() * (at(st) + at(en)) / 2.0;
+        }
+        area
+    }
+
+    /// Exponentially weighted mean, with s[0] = y[0] and
+    /// s[i] = alpha * y[i] + (1 - alpha) * s[i - 1].
+    pub fn ewm(&self, alpha: f64) -> TimeSeries<f64> {
+        assert!(alpha > 0.0 && alpha <= 1.0, "ewm alpha {alpha} must be in (0, 1]");
+        let mut prev: Option<f64> = None;
+        let data: Vec<_> = self
+            .iter()
+            .map(|&(t, y)| {
+                let s = prev.map_or(y, |prev| alpha * y + (1.0 - alpha) * prev);
+                prev = Some(s);
+                (t, s)
+            })
+            .collect();
+        Self { inner: SeriesInner::new(data) }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use chrono_tz::UTC;
     use eyre::Result;
     use pretty_assertions::assert_eq;
+    use rand::prelude::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
 
     use super::*;
+    use crate::seq::series::concat;
+    use crate::span::inc::SpanInc;
+    use crate::time::ymdhms;
 
     #[test]
     fn scalar_upper_bound_idx() -> Result<()> {
@@ -498,6 +882,643 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn scalar_stats() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        assert_eq!(series.stats(), None);
+
+        series.push((2, 4.0))?;
+        series.push((5, -2.0))?;
+        series.push((8, 7.0))?;
+
+        let stats = series.stats().unwrap();
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.min, -2.0);
+        assert_eq!(stats.max, 7.0);
+        assert_eq!(stats.sum, 9.0);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.first_x, 2);
+        assert_eq!(stats.last_x, 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_lag_lead() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        for (x, y) in [(1, 10), (2, 20), (3, 30)] {
+            series.push((x, y))?;
+        }
+
+        assert_eq!(series.lag(1).slice(), &[(1, None), (2, Some(10)), (3, Some(20))]);
+        assert_eq!(series.lead(1).slice(), &[(1, Some(20)), (2, Some(30)), (3, None)]);
+        assert_eq!(series.lag(0).dropna(), series);
+        assert_eq!(series.lag(2).slice(), &[(1, None), (2, None), (3, Some(10))]);
+        assert!(series.lead(5).ys().all(Option::is_none));
+        assert!(series.lag(usize::MAX).ys().all(Option::is_none));
+        assert!(series.lead(usize::MAX).ys().all(Option::is_none));
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_dropna() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        series.push((1, Some(10)))?;
+        series.push((2, None))?;
+        series.push((3, Some(30)))?;
+        series.push((4, None))?;
+        series.push((5, Some(50)))?;
+
+        let dropped = series.dropna();
+        assert_eq!(dropped.len(), 3);
+        assert_eq!(dropped.slice(), &[(1, 10), (3, 30), (5, 50)]);
+        assert!(ScalarSeries::<i32, Option<i32>>::new().dropna().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_map_x() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        series.push((1i64, 10))?;
+        series.push((2, 20))?;
+        series.push((4, 40))?;
+
+        let shifted = series.map_x_monotonic(|x| x + 100);
+        assert_eq!(shifted.slice(), &[(101, 10), (102, 20), (104, 40)]);
+        assert_eq!(series.map_x(|x| x + 100), shifted);
+
+        let negated = series.map_x(|x| -x);
+        assert_eq!(negated.slice(), &[(-4, 40), (-2, 20), (-1, 10)]);
+        assert!(negated.is_sorted());
+
+        let halved = series.map_x(|&x| x as f64 / 2.0);
+        assert_eq!(halved.slice(), &[(0.5, 10), (1.0, 20), (2.0, 40)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_map_x_time_op() -> Result<()> {
+        let mut series = TimeSeries::new();
+        series.push((ymdhms(2020, 1, 1, 23, 0, 0, UTC), 10))?;
+        series.push((ymdhms(2020, 1, 2, 1, 0, 0, UTC), 20))?;
+        series.push((ymdhms(2020, 1, 2, 5, 0, 0, UTC), 30))?;
+
+        let mapped = series.map_x_time_op(TimeOp::set_hour(0));
+        assert_eq!(
+            mapped.slice(),
+            &[
+                (ymdhms(2020, 1, 1, 0, 0, 0, UTC), 10),
+                (ymdhms(2020, 1, 2, 0, 0, 0, UTC), 20),
+                (ymdhms(2020, 1, 2, 0, 0, 0, UTC), 30),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_clip() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        series.push((2, 10))?;
+        series.push((5, 20))?;
+        series.push((8, 30))?;
+        series.push((11, 40))?;
+
+        let mut clipped = series.clone();
+        clipped.clip(SpanAny::unb());
+        assert_eq!(clipped, series);
+
+        clipped.clip(SpanAny::exc(3, 11));
+        assert_eq!(clipped.slice(), &[(5, 20), (8, 30)]);
+        // Original is untouched.
+        assert_eq!(series.len(), 4);
+
+        let mut clipped = series.clone();
+        clipped.clip(SpanAny::inc_unb(8));
+        assert_eq!(clipped.slice(), &[(8, 30), (11, 40)]);
+
+        let mut clipped = series.clone();
+        clipped.clip(SpanAny::exc(20, 30));
+        assert!(clipped.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_join_asof() -> Result<()> {
+        let mut trades = ScalarSeries::new();
+        trades.push((3, 'a'))?;
+        trades.push((5, 'b'))?;
+        trades.push((9, 'c'))?;
+
+        let mut quotes = ScalarSeries::new();
+        quotes.push((4, 100))?;
+        quotes.push((5, 101))?;
+        quotes.push((8, 102))?;
+
+        let joined = trades.join_asof(&quotes, |t, q| (*t, q.copied()));
+        assert_eq!(
+            joined.slice(),
+            &[(3, ('a', None)), (5, ('b', Some(101))), (9, ('c', Some(102)))]
+        );
+
+        let joined = trades.join_asof(&ScalarSeries::<i32, i32>::new(), |_, q| q.copied());
+        assert_eq!(joined.slice(), &[(3, None), (5, None), (9, None)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_windows() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        for x in 0..5 {
+            series.push((x, x * 10))?;
+        }
+
+        let windows: Vec<_> = series.windows(3).collect();
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[0], &[(0, 0), (1, 10), (2, 20)]);
+        assert_eq!(windows[2], &[(2, 20), (3, 30), (4, 40)]);
+        assert_eq!(series.windows(6).count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_ewm() -> Result<()> {
+        let mut series = TimeSeries::new();
+        series.push((ymdhms(2020, 1, 1, 0, 0, 0, UTC), 1.0))?;
+        series.push((ymdhms(2020, 1, 2, 0, 0, 0, UTC), 3.0))?;
+        series.push((ymdhms(2020, 1, 3, 0, 0, 0, UTC), 5.0))?;
+
+        let ewm = series.ewm(0.5);
+        assert_eq!(ewm.xs().collect::<Vec<_>>(), series.xs().collect::<Vec<_>>());
+        assert_eq!(ewm.ys().copied().collect::<Vec<_>>(), vec![1.0, 2.0, 3.5]);
+        assert_eq!(series.ewm(1.0), series);
+        assert!(TimeSeries::<f64>::new().ewm(0.5).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_rolling_minmax() -> Result<()> {
+        let mut r = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let mut series = TimeSeries::new();
+            let mut t = Time::zero(UTC);
+            for _ in 0..200 {
+                t += r.gen_range(1..100i64) * Duration::SEC;
+                series.push((t, r.gen_range(-50..50i32)))?;
+            }
+            let window = r.gen_range(1..500i64) * Duration::SEC;
+
+            let brute = |f: fn(i32, i32) -> i32| -> Vec<_> {
+                series
+                    .iter()
+                    .map(|&(t, _)| {
+                        let ys = series.iter().filter(|&&(x, _)| x > t - window && x <= t);
+                        (t, ys.map(|&(_, y)| y).reduce(f).unwrap())
+                    })
+                    .collect()
+            };
+            assert_eq!(series.rolling_max(window).slice(), brute(std::cmp::max));
+            assert_eq!(series.rolling_min(window).slice(), brute(std::cmp::min));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_rolling_count() -> Result<()> {
+        let mut series = TimeSeries::new();
+        for i in 0..6 {
+            series.push((Time::zero(UTC) + i * Duration::MIN, i))?;
+        }
+        let counts: Vec<_> = series.rolling_count(3 * Duration::MIN).ys().copied().collect();
+        assert_eq!(counts, [1, 2, 3, 4, 4, 4]);
+
+        // Later points with the same x are counted too.
+        series.push((Time::zero(UTC) + 5 * Duration::MIN, 6))?;
+        let counts: Vec<_> = series.rolling_count(Duration::MIN).ys().copied().collect();
+        assert_eq!(counts, [1, 2, 2, 2, 2, 3, 3]);
+        assert!(TimeSeries::<i32>::new().rolling_count(Duration::MIN).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn scalar_rolling_count_negative_window() {
+        let mut series = TimeSeries::new();
+        series.push((Time::zero(UTC), 1)).unwrap();
+        let _ = series.rolling_count(-Duration::MIN);
+    }
+
+    #[test]
+    fn scalar_rolling_minmax_large() -> Result<()> {
+        let mut series = TimeSeries::new();
+        let mut t = Time::zero(UTC);
+        for i in 0..200_000 {
+            t += Duration::SEC;
+            series.push((t, (i * 7919) % 1000))?;
+        }
+        let max = series.rolling_max(Duration::HOUR);
+        let min = series.rolling_min(Duration::HOUR);
+        assert_eq!(max.len(), series.len());
+        assert_eq!(max.last().unwrap().1, 999);
+        assert_eq!(min.last().unwrap().1, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_twap() -> Result<()> {
+        let t = |h, m| ymdhms(2020, 1, 1, h, m, 0, UTC);
+        let mut series = TimeSeries::new();
+        series.push((t(9, 0), 1.0))?;
+        series.push((t(10, 0), 4.0))?;
+
+        assert_eq!(series.twap(SpanExc::new(t(9, 0), t(12, 0))), Some(3.0));
+        assert_eq!(series.twap(SpanExc::new(t(9, 30), t(10, 30))), Some(2.5));
+        assert_eq!(series.twap(SpanExc::new(t(9, 0), t(10, 0))), Some(1.0));
+        assert_eq!(series.twap(SpanExc::new(t(10, 30), t(11, 0))), Some(4.0));
+        // Nothing is active at the start.
+        assert_eq!(series.twap(SpanExc::new(t(8, 0), t(10, 0))), None);
+        assert_eq!(series.twap(SpanExc::new(t(10, 0), t(10, 0))), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_ohlc() -> Result<()> {
+        let t = |d, h| ymdhms(2020, 1, d, h, 0, 0, UTC);
+        let mut series = TimeSeries::new();
+        // Before the anchor, so ignored.
+        series.push((t(1, 12), 100.0))?;
+        for (time, v) in [(t(2, 9), 5.0), (t(2, 10), 7.0), (t(2, 11), 3.0), (t(2, 12), 4.0)] {
+            series.push((time, v))?;
+        }
+        // Nothing on the 3rd.
+        for (time, v) in [(t(4, 0), 6.0), (t(4, 23), 6.5)] {
+            series.push((time, v))?;
+        }
+
+        let op = SpanOp::new(TimeOp::nop(), TimeOp::daily());
+        let candles = series.ohlc(op, t(2, 0));
+        assert_eq!(
+            candles.slice(),
+            &[
+                (
+                    SpanExc::new(t(2, 0), t(3, 0)),
+                    Ohlc { open: 5.0, high: 7.0, low: 3.0, close: 4.0 }
+                ),
+                (
+                    SpanExc::new(t(4, 0), t(5, 0)),
+                    Ohlc { open: 6.0, high: 6.5, low: 6.0, close: 6.5 }
+                ),
+            ]
+        );
+        assert!(TimeSeries::<f64>::new().ohlc(op, t(2, 0)).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_integrate() -> Result<()> {
+        let t = |h, m| ymdhms(2020, 1, 1, h, m, 0, UTC);
+        // Ramp of one unit per second from 9:00 to 10:00.
+        let mut series = TimeSeries::new();
+        series.push((t(9, 0), 0.0))?;
+        series.push((t(9, 30), 1800.0))?;
+        series.push((t(10, 0), 3600.0))?;
+
+        assert_eq!(series.integrate(SpanExc::new(t(9, 0), t(10, 0))), Some(3600.0 * 3600.0 / 2.0));
+        // Area of y = x from 600 to 1200.
+        assert_eq!(
+            series.integrate(SpanExc::new(t(9, 10), t(9, 20))),
+            Some((1200.0 * 1200.0 - 600.0 * 600.0) / 2.0)
+        );
+        // Clipped to the data.
+        assert_eq!(series.integrate(SpanExc::new(t(8, 0), t(11, 0))), Some(3600.0 * 3600.0 / 2.0));
+        assert_eq!(series.integrate(SpanExc::new(t(10, 0), t(11, 0))), None);
+        assert_eq!(series.integrate(SpanExc::new(t(9, 0), t(9, 0))), None);
+        assert_eq!(TimeSeries::new().integrate(SpanExc::new(t(9, 0), t(10, 0))), None);
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "must be in (0, 1]")]
+    fn scalar_ewm_invalid_alpha() {
+        let _ = TimeSeries::<f64>::new().ewm(0.0);
+    }
+
+    #[test]
+    fn scalar_validate() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        series.push((2, 10))?;
+        series.push((5, 20))?;
+        series.push((5, 25))?;
+        series.push((8, 30))?;
+        series.validate()?;
+        ScalarSeries::<i32, i32>::new().validate()?;
+
+        series.inner_mut().data_mut().swap(2, 3);
+        let err = series.validate().unwrap_err();
+        assert_eq!(err.to_string(), "out of order at index 3: 8 > 5");
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_index_span() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        assert_eq!(series.index_span(), None);
+        series.push((2, 10))?;
+        series.push((5, 20))?;
+        series.push((8, 30))?;
+        assert_eq!(series.index_span(), Some(SpanAny::inc(2, 8)));
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_is_sorted() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        assert!(series.is_sorted());
+        series.push((2, 10))?;
+        series.push((5, 20))?;
+        series.push((5, 25))?;
+        assert!(series.is_sorted());
+
+        series.inner_mut().data_mut().push((3, 30));
+        assert!(!series.is_sorted());
+        series.normalize()?;
+        assert!(series.is_sorted());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_to_span_series() -> Result<()> {
+        let mut series = TimeSeries::new();
+        series.push((ymdhms(2020, 1, 1, 9, 0, 0, UTC), 10))?;
+        series.push((ymdhms(2020, 1, 1, 9, 1, 30, UTC), 20))?;
+        series.push((ymdhms(2020, 1, 1, 9, 2, 0, UTC), 30))?;
+
+        let spans = series.to_span_series(Duration::MIN);
+        assert_eq!(spans.len(), series.len());
+        for ((span, y), (t, v)) in spans.iter().zip(series.iter()) {
+            assert_eq!(span.st, *t);
+            assert_eq!(span.size(), Duration::MIN);
+            assert_eq!(y, v);
+        }
+        // Overlapping spans are kept.
+        assert!(spans[1].0.intersect(&spans[2].0).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_xs_ys() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        series.push((2, 10))?;
+        series.push((5, 20))?;
+        series.push((8, 30))?;
+
+        let xs: Vec<_> = series.xs().collect();
+        let ys: Vec<_> = series.ys().copied().collect();
+        assert_eq!(xs, [2, 5, 8]);
+        assert_eq!(ys, [10, 20, 30]);
+        for (i, v) in series.iter().enumerate() {
+            assert_eq!((xs[i], ys[i]), *v);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_push_series() -> Result<()> {
+        let mut a = ScalarSeries::new();
+        let mut later = ScalarSeries::new();
+        let mut interleaved = ScalarSeries::new();
+        for x in 0..5 {
+            a.push((2 * x, x))?;
+            later.push((10 + x, x))?;
+            interleaved.push((2 * x + 1, x))?;
+        }
+
+        let mut appended = a.clone();
+        appended.push_series(&later)?;
+        assert_eq!(appended.slice(), concat(&[a.clone(), later]).slice());
+        assert!(appended.is_sorted());
+
+        let mut appended = a.clone();
+        appended.push_series(&interleaved)?;
+        assert_eq!(appended.slice(), concat(&[a.clone(), interleaved]).slice());
+        assert!(appended.is_sorted());
+
+        let mut appended = a.clone();
+        appended.push_series(&ScalarSeries::<i32, i32>::new())?;
+        assert_eq!(appended, a);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_concat() -> Result<()> {
+        let mut r = StdRng::seed_from_u64(0);
+        let mut shards = vec![ScalarSeries::new(); 3];
+        for (i, shard) in shards.iter_mut().enumerate() {
+            for j in 0..50 {
+                shard.push((r.gen_range(0..40), (i, j)))?;
+            }
+        }
+
+        let mut expected: Vec<_> = shards.iter().flat_map(|s| s.iter().copied()).collect();
+        expected.sort_by_key(|v| v.0);
+        assert_eq!(concat(&shards).slice(), expected);
+        assert!(concat::<ScalarSeries<i32, i32>>(&[]).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_quantile() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        assert_eq!(series.quantile(0.5), None);
+
+        for (x, y) in [(0, 3.0), (1, 1.0), (2, 4.0), (3, 2.0)] {
+            series.push((x, y))?;
+        }
+        assert_eq!(series.quantile(0.5), Some(2.5));
+        assert_eq!(series.quantile(0.0), Some(1.0));
+        assert_eq!(series.quantile(1.0), Some(4.0));
+        assert_eq!(series.quantile(1.0 / 3.0), Some(2.0));
+        assert_eq!(series.quantile(0.25), Some(1.75));
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "must be in [0, 1]")]
+    fn scalar_quantile_invalid() {
+        let _ = ScalarSeries::<i64, f64>::new().quantile(1.5);
+    }
+
+    #[test]
+    fn scalar_find_first_last() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        for (x, y) in [(0, 1), (1, 5), (2, 3), (3, 7), (4, 2)] {
+            series.push((x, y))?;
+        }
+
+        assert_eq!(series.find_first(|&y| y > 4), Some(&(1, 5)));
+        assert_eq!(series.find_last(|&y| y > 4), Some(&(3, 7)));
+        assert_eq!(series.find_first(|&y| y > 10), None);
+        assert_eq!(ScalarSeries::<i64, i64>::new().find_last(|_| true), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_head_tail() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        for x in 0..5 {
+            series.push((x, x * 10))?;
+        }
+
+        assert_eq!(series.head(2).slice(), &[(0, 0), (1, 10)]);
+        assert_eq!(series.tail(2).slice(), &[(3, 30), (4, 40)]);
+        assert_eq!(series.head(10), series);
+        assert_eq!(series.tail(10), series);
+        assert!(series.head(0).is_empty());
+        assert!(series.tail(0).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_slice_by_idx() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        for x in 0..5 {
+            series.push((x, x * 10))?;
+        }
+
+        assert_eq!(series.slice_by_idx(1..3).slice(), &[(1, 10), (2, 20)]);
+        assert_eq!(series.slice_by_idx(3..10).slice(), &[(3, 30), (4, 40)]);
+        assert!(series.slice_by_idx(7..10).is_empty());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = series.slice_by_idx(3..1);
+        assert!(reversed.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_in_span_where() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        for (x, y) in [(0, 5), (1, 12), (2, 3), (3, 15), (4, 11), (5, 20), (6, 1)] {
+            series.push((x, y))?;
+        }
+
+        assert_eq!(series.first_in_span_where(SpanAny::inc(2, 5), |&y| y > 10), Some(&(3, 15)));
+        assert_eq!(series.last_in_span_where(SpanAny::inc(2, 5), |&y| y > 10), Some(&(5, 20)));
+        assert_eq!(series.last_in_span_where(SpanAny::exc(2, 5), |&y| y > 10), Some(&(4, 11)));
+        assert_eq!(series.first_in_span_where(SpanAny::exc_unb(3), |&y| y > 10), Some(&(4, 11)));
+        assert_eq!(series.first_in_span_where(SpanAny::inc(2, 5), |&y| y > 20), None);
+        assert_eq!(series.first_in_span_where(SpanAny::exc(6, 6), |_| true), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_correlation() -> Result<()> {
+        let mut a = ScalarSeries::new();
+        let mut b = ScalarSeries::new();
+        let mut c = ScalarSeries::new();
+        for x in 0..10 {
+            a.push((x, x as f64))?;
+            b.push((x, 3.0 * x as f64 + 1.0))?;
+            c.push((x, -2.0 * x as f64))?;
+        }
+        assert!((a.correlation(&b).unwrap() - 1.0).abs() < 1e-12);
+        assert!((a.correlation(&c).unwrap() + 1.0).abs() < 1e-12);
+
+        // Only matching x values are paired.
+        let mut sparse = ScalarSeries::new();
+        sparse.push((1, 5.0))?;
+        sparse.push((4, 2.0))?;
+        sparse.push((20, 0.0))?;
+        assert!((a.correlation(&sparse).unwrap() + 1.0).abs() < 1e-12);
+
+        let mut one = ScalarSeries::new();
+        one.push((3, 1.0))?;
+        assert_eq!(a.correlation(&one), None);
+        let mut flat = ScalarSeries::new();
+        flat.push((1, 1.0))?;
+        flat.push((2, 1.0))?;
+        assert_eq!(a.correlation(&flat), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_search_by() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        for x in 0..5 {
+            series.push((x, x * 10))?;
+        }
+
+        assert_eq!(series.search_by(|v| (v.0 * 2).cmp(&6)), Ok(3));
+        assert_eq!(series.search_by(|v| (v.0 * 2).cmp(&5)), Err(3));
+        assert_eq!(series.search_by(|v| (v.0 * 2).cmp(&20)), Err(5));
+        assert_eq!(series.search_by(|v| (v.0 * 2).cmp(&-1)), Err(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_decimal_x() -> Result<()> {
+        let mut series = ScalarSeries::new();
+        for (i, x) in [dec!(0.1), dec!(0.2), dec!(0.3), dec!(0.4)].into_iter().enumerate() {
+            series.push((x, i))?;
+        }
+
+        assert_eq!(
+            series.subseq(SpanAny::inc(dec!(0.2), dec!(0.3))),
+            &[(dec!(0.2), 1), (dec!(0.3), 2)]
+        );
+        assert_eq!(series.subseq(SpanAny::exc(dec!(0.2), dec!(0.3))), &[(dec!(0.2), 1)]);
+        assert_eq!(series.subseq(SpanAny::exc_inc(dec!(0.2), dec!(0.3))), &[(dec!(0.3), 2)]);
+        assert!(series.subseq(SpanAny::exc_exc(dec!(0.2), dec!(0.3))).is_empty());
+        assert_eq!(series.subseq(SpanAny::exc_unb(dec!(0.3))), &[(dec!(0.4), 3)]);
+        assert_eq!(series.subseq(SpanAny::unb_exc(dec!(0.2))), &[(dec!(0.1), 0)]);
+        // Trailing zeros don't change the value.
+        assert_eq!(
+            series.subseq(SpanAny::inc(dec!(0.20), dec!(0.300))),
+            &[(dec!(0.2), 1), (dec!(0.3), 2)]
+        );
+
+        assert_eq!(series.lower_bound(dec!(0.2)), Some(&(dec!(0.2), 1)));
+        assert_eq!(series.upper_bound(dec!(0.2)), Some(&(dec!(0.3), 2)));
+        assert_eq!(series.lower_bound_last_idx(dec!(0.2)), Some(1));
+        assert_eq!(series.lower_bound_last_idx(dec!(0.05)), None);
+        assert_eq!(series.upper_bound(dec!(0.4)), None);
+
+        // Endpoint conversions step by a whole unit, so they're exact for
+        // integer valued x.
+        let mut series = ScalarSeries::new();
+        for x in 0..5 {
+            series.push((Decimal::from(x), x))?;
+        }
+        let span = SpanAny::exc(dec!(1), dec!(3));
+        assert_eq!(span.to_inc(), Some(SpanInc::new(dec!(1), dec!(2))));
+        assert_eq!(series.subseq(span.to_inc().unwrap().to_any()), series.subseq(span));
+
+        Ok(())
+    }
+
     #[test]
     fn scalar_subseq_unbounded_both() {
         let mut series = ScalarSeries::new();
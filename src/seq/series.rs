@@ -1,8 +1,10 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 use std::iter::Map;
-use std::ops::RangeBounds;
+use std::ops::{Range, RangeBounds};
 use std::slice::{Iter, Windows};
 
-use eyre::Result;
+use eyre::{eyre, Result};
 
 use crate::seq::inner::SeriesInner;
 use crate::span::any::SpanAny;
@@ -97,6 +99,24 @@ pub trait Series {
         self.iter().map(|v| Self::y(v))
     }
 
+    /// Returns true if the series is sorted by x value, e.g. to skip |normalize|.
+    #[must_use]
+    fn is_sorted(&self) -> bool {
+        self.windows(2).all(|w| Self::x(&w[0]) <= Self::x(&w[1]))
+    }
+
+    /// Checks the series is sorted by x value. Otherwise returns an error
+    /// naming the first out of order index.
+    fn validate(&self) -> Result<()> {
+        for (idx, w) in self.windows(2).enumerate() {
+            let (prev, cur) = (Self::x(&w[0]), Self::x(&w[1]));
+            if prev > cur {
+                return Err(eyre!("out of order at index {}: {} > {}", idx + 1, prev, cur));
+            }
+        }
+        Ok(())
+    }
+
     /// Pushes a new value into the series.
     fn push(&mut self, elt: Self::V) -> Result<()> {
         if self.unchecked_push(elt)? {
@@ -282,6 +302,27 @@ pub trait Series {
         self.make_from_inner(self.inner().subseq(st..en))
     }
 
+    /// Removes, in place, all elements which are not fully contained within
+    /// the given span.
+    fn clip(&mut self, s: SpanAny<Self::X>) {
+        if s.is_unb() {
+            return;
+        }
+        let st = if s.st.is_left_unbounded() {
+            0
+        } else {
+            self.slice().partition_point(|v| s.st > Self::span_of(v).st)
+        };
+        let en = if s.en.is_right_unbounded() {
+            self.len()
+        } else {
+            self.slice().partition_point(|v| s.en >= Self::span_of(v).en)
+        };
+        let mut data = self.inner_mut().data_mut();
+        data.truncate(en);
+        data.drain(..st);
+    }
+
     #[must_use]
     fn prefix(&self, n: usize) -> Self
     where
@@ -299,6 +340,24 @@ pub trait Series {
         self.make_from_inner(self.inner().subseq(st..))
     }
 
+    /// First |n| elements, or the whole series if it's shorter.
+    #[must_use]
+    fn head(&self, n: usize) -> Self
+    where
+        Self: Sized,
+    {
+        self.slice_by_idx(0..n)
+    }
+
+    /// Last |n| elements, or the whole series if it's shorter.
+    #[must_use]
+    fn tail(&self, n: usize) -> Self
+    where
+        Self: Sized,
+    {
+        self.suffix(n)
+    }
+
     #[must_use]
     fn subseq_idx(&self, range: impl RangeBounds<usize>) -> &[Self::V] {
         &self.slice()[(range.start_bound().cloned(), range.end_bound().cloned())]
@@ -312,6 +371,18 @@ pub trait Series {
         self.make_from_inner(self.inner().subseq(range))
     }
 
+    /// Like |subseq_idx_series|, but clamps |range| to the series rather than
+    /// panicking.
+    #[must_use]
+    fn slice_by_idx(&self, range: Range<usize>) -> Self
+    where
+        Self: Sized,
+    {
+        let en = range.end.min(self.len());
+        let st = range.start.min(en);
+        self.make_from_inner(self.inner().subseq(st..en))
+    }
+
     #[must_use]
     fn max_of<R: PartialOrd>(&self, f: impl Fn(&Self::Y) -> R) -> Option<R> {
         self.max_by(&f).map(f)
@@ -332,10 +403,83 @@ pub trait Series {
         self.iter().min_by(|a, b| f(Self::y(a)).partial_cmp(&f(Self::y(b))).unwrap()).map(Self::y)
     }
 
+    /// Binary searches the series with the comparator |f|, which must be
+    /// consistent with the series order. Same semantics as
+    /// |slice::binary_search_by|.
+    fn search_by<F: FnMut(&Self::V) -> Ordering>(&self, f: F) -> Result<usize, usize> {
+        self.slice().binary_search_by(f)
+    }
+
+    /// Returns the first element whose y value satisfies |pred|.
+    #[must_use]
+    fn find_first<F: FnMut(&Self::Y) -> bool>(&self, mut pred: F) -> Option<&Self::V> {
+        self.iter().find(|v| pred(Self::y(v)))
+    }
+
+    /// Returns the last element whose y value satisfies |pred|.
+    #[must_use]
+    fn find_last<F: FnMut(&Self::Y) -> bool>(&self, mut pred: F) -> Option<&Self::V> {
+        self.iter().rev().find(|v| pred(Self::y(v)))
+    }
+
     fn span(&self) -> SpanAny<Self::X> {
         assert!(!self.is_empty(), "span of empty series");
         SpanAny::cover(&Self::span_of(self.first().unwrap()), &Self::span_of(self.last().unwrap()))
     }
+
+    /// Like |span|, but None if the series is empty.
+    fn index_span(&self) -> Option<SpanAny<Self::X>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.span())
+        }
+    }
+}
+
+/// K-way merge of already sorted series into a single sorted series. Elements
+/// with equal x values keep the order of the input series.
+pub fn concat<S: Series + Default>(series: &[S]) -> S {
+    struct Head<X> {
+        x: X,
+        series: usize,
+        idx: usize,
+    }
+
+    impl<X: PartialOrd> PartialEq for Head<X> {
+        fn eq(&self, other: &Self) -> bool {
+            self.cmp(other) == Ordering::Equal
+        }
+    }
+
+    impl<X: PartialOrd> Eq for Head<X> {}
+
+    impl<X: PartialOrd> PartialOrd for Head<X> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl<X: PartialOrd> Ord for Head<X> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            // Reversed so the max-heap pops the smallest x, then the earliest series.
+            other.x.partial_cmp(&self.x).unwrap().then(other.series.cmp(&self.series))
+        }
+    }
+
+    let mut heap: BinaryHeap<_> = series
+        .iter()
+        .enumerate()
+        .filter_map(|(i, s)| s.first().map(|v| Head { x: S::x(v), series: i, idx: 0 }))
+        .collect();
+    let mut data = Vec::with_capacity(series.iter().map(|s| s.len()).sum());
+    while let Some(Head { series: i, idx, .. }) = heap.pop() {
+        data.push(series[i].slice()[idx].clone());
+        if let Some(v) = series[i].get(idx + 1) {
+            heap.push(Head { x: S::x(v), series: i, idx: idx + 1 });
+        }
+    }
+    S::default().make_from_inner(SeriesInner::new(data))
 }
 
 #[macro_export]
@@ -1,13 +1,16 @@
 use std::cmp::{Eq, PartialEq, PartialOrd};
 use std::hash::Hash;
 
-use eyre::Result;
+use eyre::{eyre, Result};
 
+use crate::duration::Duration;
 use crate::seq::inner::SeriesInner;
+use crate::seq::scalar_series::TimeSeries;
 use crate::seq::series::Series;
 use crate::span::any::SpanAny;
 use crate::span::endpoint::EndpointConversion;
 use crate::span::exc::SpanExc;
+use crate::time::Time;
 
 #[must_use]
 #[derive(Debug, Eq, Default, PartialEq, PartialOrd, Hash, Clone)]
@@ -19,6 +22,112 @@ impl<X: PartialOrd + Copy + std::fmt::Display, Y: Clone> SpanExcSeries<X, Y> {
     pub fn new() -> Self {
         Self { inner: SeriesInner::empty() }
     }
+
+    /// Like |push|, but errors if the span overlaps an existing one. Only the
+    /// neighbors at the insertion point are checked, so this relies on the
+    /// series already being disjoint.
+    pub fn push_disjoint(&mut self, elt: (SpanExc<X>, Y)) -> Result<()> {
+        let idx = self.slice().partition_point(|v| v.0.st <= elt.0.st);
+        let prev = idx.checked_sub(1).and_then(|idx| self.get(idx));
+        for (s, _) in prev.into_iter().chain(self.get(idx)) {
+            if s.intersect(&elt.0).is_some() {
+                return Err(eyre!("span {} overlaps existing span {}", elt.0, s));
+            }
+        }
+        self.push(elt)
+    }
+
+    /// Returns just the spans, in order.
+    #[must_use]
+    pub fn spans(&self) -> Vec<SpanExc<X>> {
+        self.iter().map(|v| v.0).collect()
+    }
+
+    /// Returns just the values, in order.
+    #[must_use]
+    pub fn values(&self) -> Vec<Y> {
+        self.iter().map(|v| v.1.clone()).collect()
+    }
+
+    /// Folds |f| over the values in order, starting from |init|.
+    #[must_use]
+    pub fn fold_values<Z, F: FnMut(Z, &Y) -> Z>(&self, init: Z, f: F) -> Z {
+        self.ys().fold(init, f)
+    }
+
+    /// Applies |f| to each span, keeping the values, and re-sorts the result.
+    pub fn map_spans<X2, F>(&self, f: F) -> SpanExcSeries<X2, Y>
+    where
+        X2: PartialOrd + Copy + std::fmt::Display,
+        F: FnMut(SpanExc<X>) -> SpanExc<X2>,
+    {
+        let mut series = self.map_spans_monotonic(f);
+        series.normalize().unwrap();
+        series
+    }
+
+    /// Like |map_spans|, but |f| must preserve the order of the spans (e.g. a
+    /// constant shift), so no re-sort is done.
+    pub fn map_spans_monotonic<X2, F>(&self, mut f: F) -> SpanExcSeries<X2, Y>
+    where
+        X2: PartialOrd + Copy + std::fmt::Display,
+        F: FnMut(SpanExc<X>) -> SpanExc<X2>,
+    {
+        let data: Vec<_> = self.iter().map(|(s, y)| (f(*s), y.clone())).collect();
+        SpanExcSeries { inner: SeriesInner::new(data) }
+    }
+
+    /// Intersects each span with |bound|, dropping spans which don't overlap it.
+    pub fn clip_spans(&self, bound: SpanExc<X>) -> Self {
+        let data: Vec<_> =
+            self.iter().filter_map(|(s, y)| s.intersect(&bound).map(|s| (s, y.clone()))).collect();
+        Self { inner: SeriesInner::new(data) }
+    }
+
+    /// Sorted merge of the spans of both series by start. Spans with equal
+    /// starts from |self| come first.
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut data = Vec::with_capacity(self.len() + other.len());
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            if y.0.st < x.0.st {
+                data.push(b.next().unwrap().clone());
+            } else {
+                data.push(a.next().unwrap().clone());
+            }
+        }
+        data.extend(a.cloned());
+        data.extend(b.cloned());
+        Self { inner: SeriesInner::new(data) }
+    }
+}
+
+impl<Y: Clone> SpanExcSeries<Time, Y> {
+    /// Samples the series at |count| times start, start + step, ... Times not
+    /// covered by any span are None. If spans overlap, the latest starting
+    /// span covering a time wins.
+    pub fn sample(&self, start: Time, step: Duration, count: usize) -> TimeSeries<Option<Y>> {
+        let data = self.slice();
+        let mut series = TimeSeries::new();
+        series.inner_mut().data_mut().extend((0..count).map(|i| {
+            let t = start + i * step;
+            // Scan back since an earlier, longer span may cover |t| even if
+            // later starting ones don't.
+            let en = data.partition_point(|(s, _)| s.st <= t);
+            let v = data[..en].iter().rev().find(|(s, _)| s.contains(&t));
+            (t, v.map(|(_, y)| y.clone()))
+        }));
+        series
+    }
+}
+
+impl SpanExcSeries<Time, f64> {
+    /// Sum of each value weighted by the length of its span in seconds, e.g.
+    /// the total of a rate over each interval.
+    #[must_use]
+    pub fn weighted_sum(&self) -> f64 {
+        self.iter().map(|(s, y)| y * s.size().secs_f64()).sum()
+    }
 }
 
 impl<X: PartialOrd + Copy + std::fmt::Display, Y: Clone> Series for SpanExcSeries<X, Y> {
@@ -123,10 +232,12 @@ impl<X: PartialOrd + Copy + std::fmt::Display + EndpointConversion, Y: Clone> Se
 
 #[cfg(test)]
 mod tests {
+    use chrono_tz::UTC;
     use eyre::Result;
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::time::ymdhms;
 
     #[test]
     fn span_exc_upper_bound_idx() -> Result<()> {
@@ -451,6 +562,196 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn span_exc_map_spans() -> Result<()> {
+        let mut series = SpanExcSeries::new();
+        series.push((SpanExc::new(2, 3), 10))?;
+        series.push((SpanExc::new(5, 6), 20))?;
+        series.push((SpanExc::new(8, 9), 30))?;
+
+        let expected =
+            [(SpanExc::new(12, 13), 10), (SpanExc::new(15, 16), 20), (SpanExc::new(18, 19), 30)];
+        let shift = |s: SpanExc<i32>| SpanExc::new(s.st + 10, s.en + 10);
+        assert_eq!(series.map_spans(shift).slice(), &expected);
+        assert_eq!(series.map_spans_monotonic(shift).slice(), &expected);
+
+        // Reversing the order of the spans requires a re-sort.
+        let mapped = series.map_spans(|s| SpanExc::new(-s.en, -s.st));
+        assert_eq!(
+            mapped.slice(),
+            &[(SpanExc::new(-9, -8), 30), (SpanExc::new(-6, -5), 20), (SpanExc::new(-3, -2), 10)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn span_exc_index_span() -> Result<()> {
+        let mut series = SpanExcSeries::new();
+        assert_eq!(series.index_span(), None);
+        series.push((SpanExc::new(0, 2), 10))?;
+        series.push((SpanExc::new(1, 5), 20))?;
+        series.push((SpanExc::new(6, 9), 30))?;
+        assert_eq!(series.index_span(), Some(SpanAny::exc(0, 9)));
+        Ok(())
+    }
+
+    #[test]
+    fn span_exc_push_disjoint() -> Result<()> {
+        let mut series = SpanExcSeries::new();
+        series.push_disjoint((SpanExc::new(4, 6), 20))?;
+        series.push_disjoint((SpanExc::new(0, 2), 10))?;
+        series.push_disjoint((SpanExc::new(6, 9), 30))?;
+        series.push_disjoint((SpanExc::new(2, 4), 15))?;
+        assert_eq!(
+            series.spans(),
+            [SpanExc::new(0, 2), SpanExc::new(2, 4), SpanExc::new(4, 6), SpanExc::new(6, 9)]
+        );
+
+        let err = series.push_disjoint((SpanExc::new(5, 7), 40)).unwrap_err();
+        assert_eq!(err.to_string(), "span [5,7) overlaps existing span [4,6)");
+        assert!(series.push_disjoint((SpanExc::new(8, 12), 40)).is_err());
+        assert!(series.push_disjoint((SpanExc::new(-1, 1), 40)).is_err());
+        assert!(series.push_disjoint((SpanExc::new(4, 6), 40)).is_err());
+        assert_eq!(series.len(), 4);
+        series.push_disjoint((SpanExc::new(9, 10), 40))?;
+        assert_eq!(series.len(), 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn span_exc_spans_values() -> Result<()> {
+        let mut series = SpanExcSeries::new();
+        series.push((SpanExc::new(0, 2), 10))?;
+        series.push((SpanExc::new(2, 5), 20))?;
+        series.push((SpanExc::new(6, 9), 30))?;
+
+        assert_eq!(series.spans(), [SpanExc::new(0, 2), SpanExc::new(2, 5), SpanExc::new(6, 9)]);
+        assert_eq!(series.values(), [10, 20, 30]);
+        assert!(SpanExcSeries::<i32, i32>::new().spans().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn span_exc_fold_values() -> Result<()> {
+        let mut series = SpanExcSeries::<i32, i32>::new();
+        assert_eq!(series.fold_values(0, |acc, y| acc + y), 0);
+        series.push((SpanExc::new(0, 2), 10))?;
+        series.push((SpanExc::new(2, 5), 20))?;
+        series.push((SpanExc::new(6, 9), 30))?;
+
+        assert_eq!(series.fold_values(0, |acc, y| acc + y), 60);
+        assert_eq!(series.fold_values(String::new(), |acc, y| acc + &y.to_string()), "102030");
+
+        Ok(())
+    }
+
+    #[test]
+    fn span_exc_clip_spans() -> Result<()> {
+        let mut series = SpanExcSeries::new();
+        series.push((SpanExc::new(0, 2), 10))?;
+        series.push((SpanExc::new(2, 5), 20))?;
+        series.push((SpanExc::new(6, 9), 30))?;
+        series.push((SpanExc::new(10, 12), 40))?;
+
+        let clipped = series.clip_spans(SpanExc::new(3, 8));
+        assert_eq!(clipped.slice(), &[(SpanExc::new(3, 5), 20), (SpanExc::new(6, 8), 30)]);
+
+        let clipped = series.clip_spans(SpanExc::new(0, 12));
+        assert_eq!(clipped, series);
+
+        let clipped = series.clip_spans(SpanExc::new(20, 30));
+        assert!(clipped.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn span_exc_merge() -> Result<()> {
+        let mut a = SpanExcSeries::new();
+        a.push((SpanExc::new(0, 2), 10))?;
+        a.push((SpanExc::new(4, 6), 20))?;
+        a.push((SpanExc::new(8, 9), 30))?;
+        let mut b = SpanExcSeries::new();
+        b.push((SpanExc::new(1, 3), 40))?;
+        b.push((SpanExc::new(4, 5), 50))?;
+        b.push((SpanExc::new(10, 12), 60))?;
+
+        let merged = a.merge(&b);
+        assert_eq!(
+            merged.slice(),
+            &[
+                (SpanExc::new(0, 2), 10),
+                (SpanExc::new(1, 3), 40),
+                (SpanExc::new(4, 6), 20),
+                (SpanExc::new(4, 5), 50),
+                (SpanExc::new(8, 9), 30),
+                (SpanExc::new(10, 12), 60),
+            ]
+        );
+
+        let mut sorted: Vec<_> = a.iter().chain(b.iter()).cloned().collect();
+        sorted.sort_by_key(|v| v.0.st);
+        assert_eq!(merged.slice(), sorted.as_slice());
+
+        assert_eq!(a.merge(&SpanExcSeries::new()), a);
+        assert_eq!(SpanExcSeries::new().merge(&b), b);
+
+        Ok(())
+    }
+
+    #[test]
+    fn span_exc_weighted_sum() -> Result<()> {
+        let t = |h| ymdhms(2020, 1, 1, h, 0, 0, UTC);
+        let mut series = SpanExcSeries::<Time, f64>::new();
+        assert_eq!(series.weighted_sum(), 0.0);
+        series.push((SpanExc::new(t(1), t(2)), 2.0))?;
+        series.push((SpanExc::new(t(2), t(5)), 0.5))?;
+        assert_eq!(series.weighted_sum(), 2.0 * 3600.0 + 0.5 * 3.0 * 3600.0);
+        Ok(())
+    }
+
+    #[test]
+    fn span_exc_sample() -> Result<()> {
+        let t = |h| ymdhms(2020, 1, 1, h, 0, 0, UTC);
+        let mut series = SpanExcSeries::new();
+        series.push((SpanExc::new(t(1), t(3)), 10))?;
+        series.push((SpanExc::new(t(4), t(6)), 20))?;
+
+        let sampled = series.sample(t(0), Duration::HOUR, 7);
+        assert_eq!(
+            sampled.slice(),
+            &[
+                (t(0), None),
+                (t(1), Some(10)),
+                (t(2), Some(10)),
+                (t(3), None),
+                (t(4), Some(20)),
+                (t(5), Some(20)),
+                (t(6), None),
+            ]
+        );
+        assert_eq!(
+            SpanExcSeries::<Time, i32>::new().sample(t(0), Duration::HOUR, 2).slice(),
+            &[(t(0), None), (t(1), None)]
+        );
+
+        // An earlier, longer span still covers times after a shorter one ends.
+        let mut series = SpanExcSeries::new();
+        series.push((SpanExc::new(t(0), t(10)), 1))?;
+        series.push((SpanExc::new(t(2), t(4)), 2))?;
+        let sampled = series.sample(t(1), 2 * Duration::HOUR, 5);
+        assert_eq!(
+            sampled.slice(),
+            &[(t(1), Some(1)), (t(3), Some(2)), (t(5), Some(1)), (t(7), Some(1)), (t(9), Some(1))]
+        );
+        assert_eq!(series.sample(t(10), Duration::HOUR, 1).slice(), &[(t(10), None)]);
+
+        Ok(())
+    }
+
     #[test]
     fn span_exc_subseq_unbounded_both() {
         let mut series = SpanExcSeries::new();
@@ -1,8 +1,14 @@
+use std::cmp::Ordering;
 use std::fmt;
+use std::marker::PhantomData;
 use std::ops::{
-    Bound, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Sub,
+    Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Sub,
 };
+use std::str::FromStr;
 
+use eyre::eyre;
+use num_traits::FromPrimitive;
+use serde::de::{self, Visitor};
 use serde::{Deserialize, Serialize};
 
 use crate::span::endpoint::{Endpoint, EndpointConversion};
@@ -23,6 +29,42 @@ impl<T: fmt::Display> fmt::Display for SpanAny<T> {
     }
 }
 
+/// Parses the |Display| form, e.g. "[0,2)" or "(-inf,5]".
+impl<T: FromStr> FromStr for SpanAny<T>
+where
+    T::Err: fmt::Display,
+{
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse =
+            |v: &str| v.trim().parse::<T>().map_err(|e| eyre!("invalid span value '{}': {}", v, e));
+        let (st, en) = s.trim().split_once(',').ok_or_else(|| eyre!("span '{}' missing ','", s))?;
+
+        let st = if st == "(-inf" {
+            Endpoint::Unbounded { left: true }
+        } else if let Some(p) = st.strip_prefix('(') {
+            Endpoint::Open { p: parse(p)?, left: true }
+        } else if let Some(p) = st.strip_prefix('[') {
+            Endpoint::Closed { p: parse(p)?, left: true }
+        } else {
+            return Err(eyre!("span '{}' must start with '(' or '['", s));
+        };
+
+        let en = if en == "+inf)" {
+            Endpoint::Unbounded { left: false }
+        } else if let Some(p) = en.strip_suffix(')') {
+            Endpoint::Open { p: parse(p)?, left: false }
+        } else if let Some(p) = en.strip_suffix(']') {
+            Endpoint::Closed { p: parse(p)?, left: false }
+        } else {
+            return Err(eyre!("span '{}' must end with ')' or ']'", s));
+        };
+
+        Ok(Self::new(st, en))
+    }
+}
+
 impl<T: Default> SpanAny<T> {
     pub fn empty() -> Self {
         Self::exc(T::default(), T::default())
@@ -34,6 +76,12 @@ impl<T> SpanAny<T> {
         Self { st, en }
     }
 
+    /// True if both endpoints have values.
+    #[must_use]
+    pub fn is_bounded(&self) -> bool {
+        !self.st.is_unbounded() && !self.en.is_unbounded()
+    }
+
     /// Exclusive-exclusive.
     pub const fn exc_exc(st: T, en: T) -> Self {
         Self { st: Endpoint::Open { p: st, left: true }, en: Endpoint::Open { p: en, left: false } }
@@ -98,6 +146,18 @@ impl<T: PartialOrd> SpanAny<T> {
         &self.st <= t && &self.en >= t
     }
 
+    /// True if every value is in the span. Vacuously true for no values.
+    #[must_use]
+    pub fn contains_all(&self, values: &[T]) -> bool {
+        values.iter().all(|v| self.contains(v))
+    }
+
+    /// True if at least one value is in the span.
+    #[must_use]
+    pub fn contains_any(&self, values: &[T]) -> bool {
+        values.iter().any(|v| self.contains(v))
+    }
+
     #[must_use]
     pub fn contains_span(&self, s: &Self) -> bool {
         self.st <= s.st && self.en >= s.en
@@ -123,6 +183,47 @@ impl<T: PartialOrd> SpanAny<T> {
     }
 }
 
+impl<T: PartialOrd + Into<f64> + Copy> SpanAny<T> {
+    /// Position of |t| in the span, from 0.0 at the start to 1.0 at the end,
+    /// clamped to that range. None if the span is unbounded, empty, or a point.
+    #[must_use]
+    pub fn proportion(&self, t: T) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        let st: f64 = (*self.st.value()?).into();
+        let en: f64 = (*self.en.value()?).into();
+        if en <= st {
+            return None;
+        }
+        Some(((t.into() - st) / (en - st)).clamp(0.0, 1.0))
+    }
+}
+
+impl<T: PartialOrd + Into<f64> + FromPrimitive + Copy> SpanAny<T> {
+    /// Splits the span at |frac| of the way from its start to its end, into
+    /// [st, p) and [p, en), keeping the outer endpoints as they are. The split
+    /// point is converted back with |FromPrimitive::from_f64|, which truncates
+    /// integers towards zero. None if the span is unbounded or empty, or
+    /// |frac| isn't in [0, 1].
+    #[must_use]
+    pub fn split_at_proportion(&self, frac: f64) -> Option<(Self, Self)> {
+        if self.is_empty() || !(0.0..=1.0).contains(&frac) {
+            return None;
+        }
+        let st: f64 = (*self.st.value()?).into();
+        let en: f64 = (*self.en.value()?).into();
+        let p = T::from_f64(st + frac * (en - st))?;
+        // Splitting at an open start mustn't pull the start into the second half.
+        let second_st = if self.st.is_open() && self.st.value() == Some(&p) {
+            self.st
+        } else {
+            Endpoint::Closed { p, left: true }
+        };
+        Some((Self::new(self.st, Endpoint::Open { p, left: false }), Self::new(second_st, self.en)))
+    }
+}
+
 impl<T: EndpointConversion + Sub + Copy> SpanAny<T> {
     #[must_use]
     pub fn size(&self) -> Option<T::Output> {
@@ -131,6 +232,18 @@ impl<T: EndpointConversion + Sub + Copy> SpanAny<T> {
 }
 
 impl<T: EndpointConversion + Copy> SpanAny<T> {
+    /// Smallest value contained in the span, or None if unbounded on the left.
+    #[must_use]
+    pub fn lower_value(&self) -> Option<T> {
+        self.st.to_closed()
+    }
+
+    /// Largest value contained in the span, or None if unbounded on the right.
+    #[must_use]
+    pub fn upper_value(&self) -> Option<T> {
+        self.en.to_closed()
+    }
+
     #[must_use]
     pub fn to_inc(&self) -> Option<SpanInc<T>> {
         Some(SpanInc::new(self.st.to_closed()?, self.en.to_closed()?))
@@ -179,12 +292,51 @@ impl<T: EndpointConversion + Copy> SpanAny<T> {
     }
 }
 
+impl<T: EndpointConversion + PartialOrd + Copy> SpanAny<T> {
+    /// Returns true if |other| starts at the first value after the end of this
+    /// span, so they touch without overlapping, e.g. [0,3) and [3,5].
+    #[must_use]
+    pub fn is_adjacent(&self, other: &Self) -> bool {
+        match (self.en.to_open(), other.st.to_closed()) {
+            (Some(en), Some(st)) => en == st,
+            _ => false,
+        }
+    }
+}
+
+impl<T: EndpointConversion + Copy + fmt::Display> SpanAny<T> {
+    /// Like |to_exc|, but with a descriptive error for unbounded spans.
+    pub fn require_bounded(&self) -> eyre::Result<SpanExc<T>> {
+        if !self.is_bounded() {
+            return Err(eyre!("span {} is unbounded", self));
+        }
+        self.to_exc().ok_or_else(|| eyre!("span {} can't be represented as [st,en)", self))
+    }
+}
+
+impl<T: Clone> SpanAny<T> {
+    /// Converts any range, e.g. `2..8` or `..=5`, into a span.
+    pub fn from_range_bounds<R: RangeBounds<T>>(r: R) -> Self {
+        Self::new(
+            Endpoint::from_bound(r.start_bound().cloned(), true),
+            Endpoint::from_bound(r.end_bound().cloned(), false),
+        )
+    }
+}
+
 impl<T: Copy> SpanAny<T> {
     #[must_use]
     pub fn to_bounds(&self) -> (Bound<T>, Bound<T>) {
         (self.st.bound().cloned(), self.en.bound().cloned())
     }
 
+    /// Values of the start and end endpoints, ignoring whether they're open
+    /// or closed. None for an unbounded side.
+    #[must_use]
+    pub fn endpoint_values(&self) -> (Option<T>, Option<T>) {
+        (self.st.value().copied(), self.en.value().copied())
+    }
+
     pub fn point(p: T) -> Self {
         Self { st: Endpoint::Closed { p, left: true }, en: Endpoint::Closed { p, left: false } }
     }
@@ -201,6 +353,19 @@ impl<T: PartialOrd + Copy> SpanAny<T> {
         }
     }
 
+    /// Common overlap of all |spans|, or None if there is none. Intersecting
+    /// nothing gives the unbounded span.
+    #[must_use]
+    pub fn intersect_all<I: IntoIterator<Item = Self>>(spans: I) -> Option<Self> {
+        spans.into_iter().try_fold(Self::unb(), |acc, s| acc.intersect(&s))
+    }
+
+    /// Smallest span containing both this span and the point |t|. An empty
+    /// span becomes just |t|.
+    pub fn cover_point(&self, t: T) -> Self {
+        Self::cover(self, &Self::point(t))
+    }
+
     #[must_use]
     pub fn intersect(&self, s: &Self) -> Option<Self> {
         let span = Self::new(pmax(&self.st, &s.st), pmin(&self.en, &s.en));
@@ -212,6 +377,27 @@ impl<T: PartialOrd + Copy> SpanAny<T> {
     }
 }
 
+/// Sorts |spans| by start and merges any that overlap or touch, leaving the
+/// minimal set of disjoint spans covering the same points. Empty spans are
+/// dropped.
+pub fn sort_and_merge<T: PartialOrd + Copy>(spans: &mut Vec<SpanAny<T>>) {
+    spans.retain(|s| !s.is_empty());
+    spans.sort_by(|a, b| a.st.partial_cmp(&b.st).unwrap_or(Ordering::Equal));
+    let mut merged: Vec<SpanAny<T>> = Vec::with_capacity(spans.len());
+    for s in spans.drain(..) {
+        match merged.last_mut() {
+            Some(last) if touches(&last.en, &s.st) => last.en = pmax(&last.en, &s.en),
+            _ => merged.push(s),
+        }
+    }
+    *spans = merged;
+}
+
+/// Whether a span ending at |en| and one starting at |st| leave no gap between them.
+fn touches<T: PartialOrd>(en: &Endpoint<T>, st: &Endpoint<T>) -> bool {
+    st <= en || ((en.is_closed() || st.is_closed()) && en.value() == st.value())
+}
+
 impl<T> From<(Bound<T>, Bound<T>)> for SpanAny<T> {
     fn from(v: (Bound<T>, Bound<T>)) -> Self {
         Self::new(Endpoint::from_bound(v.0, true), Endpoint::from_bound(v.1, false))
@@ -227,6 +413,59 @@ impl<T: Copy> From<(Bound<&T>, Bound<&T>)> for SpanAny<T> {
     }
 }
 
+/// Wrapper which serializes a |SpanAny| as its display string, e.g. "[0,2)",
+/// rather than as a struct of two endpoints.
+#[must_use]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct SpanDisplay<T>(pub SpanAny<T>);
+
+impl<T> From<SpanAny<T>> for SpanDisplay<T> {
+    fn from(v: SpanAny<T>) -> Self {
+        Self(v)
+    }
+}
+
+impl<T> From<SpanDisplay<T>> for SpanAny<T> {
+    fn from(v: SpanDisplay<T>) -> Self {
+        v.0
+    }
+}
+
+impl<'a, T: FromStr> Deserialize<'a> for SpanDisplay<T>
+where
+    T::Err: fmt::Display,
+{
+    fn deserialize<D: serde::Deserializer<'a>>(d: D) -> Result<Self, D::Error> {
+        struct SpanVisitor<T>(PhantomData<T>);
+
+        impl<T: FromStr> Visitor<'_> for SpanVisitor<T>
+        where
+            T::Err: fmt::Display,
+        {
+            type Value = SpanDisplay<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("span")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<SpanDisplay<T>, E>
+            where
+                E: de::Error,
+            {
+                v.parse().map(SpanDisplay).map_err(E::custom)
+            }
+        }
+
+        d.deserialize_string(SpanVisitor(PhantomData))
+    }
+}
+
+impl<T: fmt::Display> Serialize for SpanDisplay<T> {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&self.0.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -596,4 +835,190 @@ mod tests {
         assert_eq!(unb_unb.size(), None);
         assert_eq!(empty.size(), Some(0));
     }
+
+    #[test]
+    fn values() {
+        assert_eq!(SpanAny::<i64>::exc(0, 3).lower_value(), Some(0));
+        assert_eq!(SpanAny::<i64>::exc(0, 3).upper_value(), Some(2));
+        assert_eq!(SpanAny::<i64>::exc_inc(0, 3).lower_value(), Some(1));
+        assert_eq!(SpanAny::<i64>::exc_inc(0, 3).upper_value(), Some(3));
+        assert_eq!(SpanAny::<i64>::unb_exc(3).lower_value(), None);
+        assert_eq!(SpanAny::<i64>::unb_exc(3).upper_value(), Some(2));
+        assert_eq!(SpanAny::<i64>::inc_unb(0).lower_value(), Some(0));
+        assert_eq!(SpanAny::<i64>::inc_unb(0).upper_value(), None);
+    }
+
+    #[test]
+    fn contains_all_any() {
+        let span = SpanAny::<i64>::inc(0, 10);
+        assert!(span.contains_all(&[1, 5, 9]));
+        assert!(span.contains_any(&[1, 5, 9]));
+        assert!(!span.contains_all(&[1, 5, 9, 11]));
+        assert!(span.contains_any(&[1, 5, 9, 11]));
+        assert!(!span.contains_any(&[-1, 11]));
+        assert!(span.contains_all(&[]));
+        assert!(!span.contains_any(&[]));
+        assert!(SpanAny::<i64>::exc(0, 10).contains_all(&[0]));
+        assert!(!SpanAny::<i64>::exc(0, 10).contains_any(&[10]));
+    }
+
+    #[test]
+    fn proportion() {
+        let span = SpanAny::<i32>::inc(0, 10);
+        assert_eq!(span.proportion(5), Some(0.5));
+        assert_eq!(span.proportion(0), Some(0.0));
+        assert_eq!(span.proportion(10), Some(1.0));
+        assert_eq!(span.proportion(-5), Some(0.0));
+        assert_eq!(span.proportion(15), Some(1.0));
+        assert_eq!(SpanAny::<f64>::exc(1.0, 3.0).proportion(1.5), Some(0.25));
+        assert_eq!(SpanAny::<i32>::inc_unb(0).proportion(5), None);
+        assert_eq!(SpanAny::<i32>::unb_inc(10).proportion(5), None);
+        assert_eq!(SpanAny::<i32>::point(5).proportion(5), None);
+        assert_eq!(SpanAny::<i32>::inc(10, 0).proportion(5), None);
+    }
+
+    #[test]
+    fn bounded() -> eyre::Result<()> {
+        assert!(SpanAny::<i64>::exc(0, 2).is_bounded());
+        assert!(SpanAny::<i64>::exc_exc(0, 2).is_bounded());
+        assert!(!SpanAny::<i64>::unb_exc(2).is_bounded());
+        assert!(!SpanAny::<i64>::inc_unb(2).is_bounded());
+        assert!(!SpanAny::<i64>::unb().is_bounded());
+
+        assert_eq!(SpanAny::<i64>::inc(0, 2).require_bounded()?, SpanExc::new(0, 3));
+        let err = SpanAny::<i64>::unb_exc(2).require_bounded().unwrap_err();
+        assert_eq!(err.to_string(), "span (-inf,2) is unbounded");
+        assert!(SpanAny::<i64>::inc(0, i64::MAX).require_bounded().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn display_serialization() -> eyre::Result<()> {
+        let span = SpanDisplay(SpanAny::<i64>::exc(0, 2));
+        let se = serde_json::to_string(&span)?;
+        assert_eq!(se, "\"[0,2)\"");
+        let de: SpanDisplay<i64> = serde_json::from_str(&se)?;
+        assert_eq!(de, span);
+
+        for span in [
+            SpanAny::<i64>::exc_exc(-1, 2),
+            SpanAny::inc(0, 2),
+            SpanAny::unb_inc(5),
+            SpanAny::exc_unb(5),
+            SpanAny::unb(),
+        ] {
+            assert_eq!(span.to_string().parse::<SpanAny<i64>>()?, span);
+        }
+        assert!("0,2)".parse::<SpanAny<i64>>().is_err());
+        assert!("[0;2)".parse::<SpanAny<i64>>().is_err());
+        assert!("[a,2)".parse::<SpanAny<i64>>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn sort_and_merge_spans() {
+        let mut spans = vec![
+            SpanAny::exc(5, 7),
+            SpanAny::exc_inc(12, 13),
+            SpanAny::exc(0, 2),
+            SpanAny::exc(11, 12),
+            SpanAny::inc(3, 4),
+            SpanAny::exc(20, 20),
+            SpanAny::exc(1, 3),
+            SpanAny::exc_exc(4, 5),
+            SpanAny::exc(10, 11),
+        ];
+        sort_and_merge(&mut spans);
+        assert_eq!(spans, [SpanAny::exc(0, 7), SpanAny::exc(10, 12), SpanAny::exc_inc(12, 13)]);
+
+        let mut spans = vec![SpanAny::inc_unb(0), SpanAny::exc(-5, 5), SpanAny::unb_exc(-5)];
+        sort_and_merge(&mut spans);
+        assert_eq!(spans, [SpanAny::unb()]);
+
+        let mut spans: Vec<SpanAny<i64>> = vec![];
+        sort_and_merge(&mut spans);
+        assert!(spans.is_empty());
+    }
+
+    #[test]
+    fn from_range_bounds() {
+        assert_eq!(SpanAny::from_range_bounds(2..8), SpanAny::exc(2, 8));
+        assert_eq!(SpanAny::from_range_bounds(2..=8), SpanAny::inc(2, 8));
+        assert_eq!(SpanAny::from_range_bounds(..5), SpanAny::unb_exc(5));
+        assert_eq!(SpanAny::from_range_bounds(..=5), SpanAny::unb_inc(5));
+        assert_eq!(SpanAny::from_range_bounds(2..), SpanAny::inc_unb(2));
+        assert_eq!(SpanAny::<i64>::from_range_bounds(..), SpanAny::unb());
+    }
+
+    #[test]
+    fn cover_point() {
+        assert_eq!(SpanAny::inc(2, 4).cover_point(6), SpanAny::inc(2, 6));
+        assert_eq!(SpanAny::inc(2, 4).cover_point(0), SpanAny::inc(0, 4));
+        assert_eq!(SpanAny::inc(2, 4).cover_point(3), SpanAny::inc(2, 4));
+        assert_eq!(SpanAny::exc(2, 4).cover_point(4), SpanAny::inc(2, 4));
+        assert_eq!(SpanAny::exc_exc(2, 4).cover_point(2), SpanAny::exc(2, 4));
+        assert_eq!(SpanAny::exc(3, 3).cover_point(7), SpanAny::point(7));
+        assert_eq!(SpanAny::unb_exc(2).cover_point(5), SpanAny::unb_inc(5));
+    }
+
+    #[test]
+    fn intersect_all() {
+        let spans = [SpanAny::exc(0, 10), SpanAny::inc(2, 8), SpanAny::exc_unb(5)];
+        assert_eq!(SpanAny::intersect_all(spans), Some(SpanAny::exc_inc(5, 8)));
+        let spans = [SpanAny::exc(0, 10), SpanAny::inc(2, 8), SpanAny::exc_exc(8, 9)];
+        assert_eq!(SpanAny::intersect_all(spans), None);
+        assert_eq!(SpanAny::<i64>::intersect_all([]), Some(SpanAny::unb()));
+        assert_eq!(SpanAny::intersect_all(vec![SpanAny::exc(1, 2)]), Some(SpanAny::exc(1, 2)));
+    }
+
+    #[test]
+    fn is_adjacent() {
+        assert!(SpanAny::exc(0, 3).is_adjacent(&SpanAny::inc(3, 5)));
+        assert!(!SpanAny::exc(0, 3).is_adjacent(&SpanAny::inc(4, 5)));
+        assert!(!SpanAny::inc(3, 5).is_adjacent(&SpanAny::exc(0, 3)));
+        assert!(SpanAny::inc(0, 2).is_adjacent(&SpanAny::inc(3, 5)));
+        assert!(SpanAny::inc(0, 2).is_adjacent(&SpanAny::exc_exc(2, 5)));
+        assert!(!SpanAny::inc(0, 3).is_adjacent(&SpanAny::inc(3, 5)));
+        assert!(!SpanAny::unb().is_adjacent(&SpanAny::inc(3, 5)));
+        assert!(!SpanAny::exc(0, 3).is_adjacent(&SpanAny::unb_inc(5)));
+    }
+
+    #[test]
+    fn split_at_proportion() {
+        let span = SpanAny::<i32>::inc(0, 10);
+        assert_eq!(span.split_at_proportion(0.3), Some((SpanAny::exc(0, 3), SpanAny::inc(3, 10))));
+        // Truncated towards zero.
+        assert_eq!(span.split_at_proportion(0.35), Some((SpanAny::exc(0, 3), SpanAny::inc(3, 10))));
+        assert_eq!(
+            span.split_at_proportion(1.0),
+            Some((SpanAny::exc(0, 10), SpanAny::inc(10, 10)))
+        );
+        assert_eq!(
+            SpanAny::<f64>::exc_exc(0.0, 2.0).split_at_proportion(0.25),
+            Some((SpanAny::exc_exc(0.0, 0.5), SpanAny::exc(0.5, 2.0)))
+        );
+        // The halves still partition a span with an open start.
+        assert_eq!(
+            SpanAny::<f64>::exc_exc(0.0, 2.0).split_at_proportion(0.0),
+            Some((SpanAny::exc_exc(0.0, 0.0), SpanAny::exc_exc(0.0, 2.0)))
+        );
+        assert_eq!(
+            SpanAny::<i32>::exc_inc(0, 10).split_at_proportion(0.05),
+            Some((SpanAny::exc_exc(0, 0), SpanAny::exc_inc(0, 10)))
+        );
+        assert_eq!(span.split_at_proportion(0.0), Some((SpanAny::exc(0, 0), SpanAny::inc(0, 10))));
+        assert_eq!(span.split_at_proportion(1.5), None);
+        assert_eq!(span.split_at_proportion(f64::NAN), None);
+        assert_eq!(SpanAny::<i32>::inc_unb(0).split_at_proportion(0.5), None);
+        assert_eq!(SpanAny::<i32>::exc(5, 5).split_at_proportion(0.5), None);
+    }
+
+    #[test]
+    fn endpoint_values() {
+        assert_eq!(SpanAny::exc(0, 2).endpoint_values(), (Some(0), Some(2)));
+        assert_eq!(SpanAny::exc_inc(0, 2).endpoint_values(), (Some(0), Some(2)));
+        assert_eq!(SpanAny::unb_inc(5).endpoint_values(), (None, Some(5)));
+        assert_eq!(SpanAny::exc_unb(5).endpoint_values(), (Some(5), None));
+        assert_eq!(SpanAny::<i32>::unb().endpoint_values(), (None, None));
+    }
 }
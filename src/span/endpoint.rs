@@ -6,6 +6,14 @@ use derive_more::IsVariant;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+/// Which side of a span an endpoint is on.
+#[must_use]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Serialize, Deserialize)]
+pub enum EndpointSide {
+    Left,
+    Right,
+}
+
 /// Represents an endpoint of a span. For comparison, endpoints behave as closed
 /// points - that is, an open endpoint should be compared with >= and <=.
 #[must_use]
@@ -43,6 +51,15 @@ impl<T> Endpoint<T> {
         }
     }
 
+    /// Applies |f| to the point, keeping the kind and side of the endpoint.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Endpoint<U> {
+        match self {
+            Endpoint::Open { p, left } => Endpoint::Open { p: f(p), left },
+            Endpoint::Closed { p, left } => Endpoint::Closed { p: f(p), left },
+            Endpoint::Unbounded { left } => Endpoint::Unbounded { left },
+        }
+    }
+
     pub const fn value(&self) -> Option<&T> {
         match self {
             Endpoint::Closed { p, .. } | Endpoint::Open { p, .. } => Some(p),
@@ -62,6 +79,14 @@ impl<T> Endpoint<T> {
         !self.is_left()
     }
 
+    pub const fn side(&self) -> EndpointSide {
+        if self.is_left() {
+            EndpointSide::Left
+        } else {
+            EndpointSide::Right
+        }
+    }
+
     pub const fn is_left_unbounded(&self) -> bool {
         match self {
             Endpoint::Unbounded { left } => *left,
@@ -77,6 +102,16 @@ impl<T> Endpoint<T> {
     }
 }
 
+impl<T: PartialOrd> Endpoint<T> {
+    /// Compares this endpoint against the value |v|, treating open endpoints
+    /// as just past their point on the inside of the span. Same as the
+    /// `PartialOrd<T>` impl.
+    #[must_use]
+    pub fn cmp_value(&self, v: &T) -> Option<Ordering> {
+        <Self as PartialOrd<T>>::partial_cmp(self, v)
+    }
+}
+
 impl<T: Clone> From<Endpoint<T>> for Bound<T> {
     fn from(value: Endpoint<T>) -> Self {
         value.bound().cloned()
@@ -480,4 +515,64 @@ mod tests {
         assert_eq!(right_unbounded.cmp(&left_unbounded), Ordering::Greater);
         assert_eq!(right_unbounded.cmp(&right_unbounded), Ordering::Equal);
     }
+
+    #[test]
+    fn map() {
+        assert_eq!(
+            Endpoint::Closed { p: 1, left: true }.map(f64::from),
+            Endpoint::Closed { p: 1.0, left: true }
+        );
+        assert_eq!(
+            Endpoint::Open { p: 1, left: false }.map(|p| p * 2),
+            Endpoint::Open { p: 2, left: false }
+        );
+        assert_eq!(
+            Endpoint::<i32>::Unbounded { left: false }.map(|_| -> f64 { panic!("called") }),
+            Endpoint::Unbounded { left: false }
+        );
+    }
+
+    #[test]
+    fn closedness_and_side() {
+        let closed = Endpoint::Closed { p: 1, left: true };
+        let open = Endpoint::Open { p: 1, left: false };
+        let unb = Endpoint::<i32>::Unbounded { left: false };
+
+        assert!(closed.is_closed() && !closed.is_open());
+        assert!(open.is_open() && !open.is_closed());
+        assert!(!unb.is_open() && !unb.is_closed());
+
+        assert_eq!(closed.side(), EndpointSide::Left);
+        assert_eq!(open.side(), EndpointSide::Right);
+        assert_eq!(unb.side(), EndpointSide::Right);
+        assert_eq!(Endpoint::<i32>::Unbounded { left: true }.side(), EndpointSide::Left);
+    }
+
+    #[test]
+    fn cmp_value() {
+        assert_eq!(Endpoint::Closed { p: 1, left: true }.cmp_value(&1), Some(Ordering::Equal));
+        assert_eq!(Endpoint::Open { p: 1, left: true }.cmp_value(&1), Some(Ordering::Greater));
+        assert_eq!(Endpoint::Open { p: 1, left: false }.cmp_value(&1), Some(Ordering::Less));
+        assert_eq!(Endpoint::Open { p: 1, left: false }.cmp_value(&0), Some(Ordering::Greater));
+        assert_eq!(Endpoint::Unbounded { left: true }.cmp_value(&i32::MIN), Some(Ordering::Less));
+        assert_eq!(
+            Endpoint::Unbounded { left: false }.cmp_value(&i32::MAX),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(Endpoint::Closed { p: f64::NAN, left: true }.cmp_value(&1.0), None);
+    }
+
+    #[test]
+    fn bound_round_trip() {
+        for left in [true, false] {
+            for e in [
+                Endpoint::Open { p: 1, left },
+                Endpoint::Closed { p: 1, left },
+                Endpoint::Unbounded { left },
+            ] {
+                assert_eq!(Endpoint::from_bound(e.bound().cloned(), left), e);
+                assert_eq!(Endpoint::from_bound(Bound::from(e), left), e);
+            }
+        }
+    }
 }
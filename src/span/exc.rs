@@ -1,5 +1,5 @@
 use std::fmt;
-use std::ops::{Bound, Range, RangeInclusive, Sub};
+use std::ops::{Add, AddAssign, Bound, Range, RangeInclusive, Sub, SubAssign};
 
 use serde::{Deserialize, Serialize};
 
@@ -67,6 +67,12 @@ impl<T: PartialOrd> SpanExc<T> {
         &self.st <= t && &self.en > t
     }
 
+    /// Like |contains|, but excludes both endpoints.
+    #[must_use]
+    pub fn contains_interior(&self, t: &T) -> bool {
+        &self.st < t && &self.en > t
+    }
+
     #[must_use]
     pub fn contains_span(&self, s: &Self) -> bool {
         self.st <= s.st && self.en >= s.en
@@ -117,6 +123,22 @@ impl<T: EndpointConversion + Copy> SpanExc<T> {
     }
 }
 
+impl<T: EndpointConversion + PartialOrd + Copy> SpanExc<T> {
+    /// Clamps |t| into the span: |st| if below it, the largest contained value
+    /// if at or past |en|, otherwise |t|. Panics if the span is empty.
+    #[must_use]
+    pub fn clamp_value(&self, t: T) -> T {
+        assert!(!self.is_empty(), "can't clamp into an empty span");
+        if t < self.st {
+            self.st
+        } else if t >= self.en {
+            self.en.to_closed(false).unwrap()
+        } else {
+            t
+        }
+    }
+}
+
 impl<T: Sub + Copy> SpanExc<T> {
     #[must_use]
     pub fn size(&self) -> T::Output {
@@ -124,6 +146,58 @@ impl<T: Sub + Copy> SpanExc<T> {
     }
 }
 
+impl<T: Add<Output = T> + Sub<Output = T> + PartialOrd + Copy> SpanExc<T> {
+    /// Moves both endpoints inward by |amount|, or None if that would leave
+    /// the span empty.
+    #[must_use]
+    pub fn shrink(&self, amount: T) -> Option<Self> {
+        let span = Self::new(self.st + amount, self.en - amount);
+        if span.is_empty() {
+            None
+        } else {
+            Some(span)
+        }
+    }
+}
+
+impl<U: Copy, T: Add<U, Output = T>> Add<U> for SpanExc<T> {
+    type Output = SpanExc<T>;
+
+    fn add(self, other: U) -> Self::Output {
+        Self::new(self.st + other, self.en + other)
+    }
+}
+
+impl<U: Copy, T: AddAssign<U>> AddAssign<U> for SpanExc<T> {
+    fn add_assign(&mut self, other: U) {
+        self.st += other;
+        self.en += other;
+    }
+}
+
+impl<U: Copy, T: Sub<U, Output = T>> Sub<U> for SpanExc<T> {
+    type Output = SpanExc<T>;
+
+    fn sub(self, other: U) -> Self::Output {
+        Self::new(self.st - other, self.en - other)
+    }
+}
+
+impl<U: Copy, T: SubAssign<U>> SubAssign<U> for SpanExc<T> {
+    fn sub_assign(&mut self, other: U) {
+        self.st -= other;
+        self.en -= other;
+    }
+}
+
+/// Panics if |p| has no successor, e.g. `i64::MAX`. Use |SpanExc::point| to
+/// handle that case.
+impl<T: EndpointConversion + Copy> From<T> for SpanExc<T> {
+    fn from(p: T) -> Self {
+        Self::point(p).expect("point has no exclusive end")
+    }
+}
+
 impl<T> From<Range<T>> for SpanExc<T> {
     fn from(r: Range<T>) -> Self {
         Self::new(r.start, r.end)
@@ -388,4 +462,52 @@ mod tests {
         assert_eq!(inc_3_5.size(), 3);
         assert_eq!(empty.size(), 0);
     }
+
+    #[test]
+    fn translate() {
+        let mut span = SpanExc::<i64>::new(0, 2);
+        assert_eq!(span + 5, SpanExc::new(5, 7));
+        assert_eq!(span + 5 - 5, span);
+        assert_eq!(span - 3, SpanExc::new(-3, -1));
+
+        span += 5;
+        assert_eq!(span, SpanExc::new(5, 7));
+        span -= 5;
+        assert_eq!(span, SpanExc::new(0, 2));
+    }
+
+    #[test]
+    fn contains_interior() {
+        let s = SpanExc::new(0, 2);
+        assert!(s.contains(&0));
+        assert!(!s.contains_interior(&0));
+        assert!(s.contains_interior(&1));
+        assert!(!s.contains_interior(&2));
+    }
+
+    #[test]
+    fn clamp_value() {
+        let s = SpanExc::new(0, 5);
+        assert_eq!(s.clamp_value(-3), 0);
+        assert_eq!(s.clamp_value(0), 0);
+        assert_eq!(s.clamp_value(3), 3);
+        assert_eq!(s.clamp_value(5), 4);
+        assert_eq!(s.clamp_value(100), 4);
+    }
+
+    #[test]
+    fn from_point() {
+        assert_eq!(SpanExc::from(5), SpanExc::new(5, 6));
+        assert!(SpanExc::from(5).contains(&5));
+        assert_eq!(SpanExc::point(i64::MAX), None);
+    }
+
+    #[test]
+    fn shrink() {
+        assert_eq!(SpanExc::new(0, 10).shrink(3), Some(SpanExc::new(3, 7)));
+        assert_eq!(SpanExc::new(0, 10).shrink(0), Some(SpanExc::new(0, 10)));
+        assert_eq!(SpanExc::new(0, 10).shrink(5), None);
+        assert_eq!(SpanExc::new(0, 3).shrink(2), None);
+        assert_eq!(SpanExc::new(0.0, 1.0).shrink(0.25), Some(SpanExc::new(0.25, 0.75)));
+    }
 }
@@ -1,5 +1,5 @@
 use std::fmt;
-use std::ops::{Bound, Range, RangeInclusive, Sub};
+use std::ops::{Add, AddAssign, Bound, Range, RangeInclusive, Sub, SubAssign};
 
 use serde::{Deserialize, Serialize};
 
@@ -9,9 +9,7 @@ use crate::span::exc::SpanExc;
 use crate::span::ops::{pmax, pmin};
 
 #[must_use]
-#[derive(
-    Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Serialize, Deserialize,
-)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Serialize, Deserialize)]
 pub struct SpanInc<T> {
     pub st: T,
     pub en: T,
@@ -30,6 +28,16 @@ impl<T: Default + EndpointConversion> SpanInc<T> {
     }
 }
 
+/// The default span is empty, consistent with `SpanExc`. This is |empty| if
+/// possible, otherwise e.g. [1,0] for unsigned types.
+impl<T: Default + EndpointConversion> Default for SpanInc<T> {
+    fn default() -> Self {
+        Self::empty()
+            .or_else(|| T::default().to_closed(true).map(|st| Self::new(st, T::default())))
+            .expect("no empty span representable")
+    }
+}
+
 impl<T> SpanInc<T> {
     pub const fn new(st: T, en: T) -> Self {
         Self { st, en }
@@ -72,6 +80,12 @@ impl<T: PartialOrd> SpanInc<T> {
         &self.st <= t && &self.en >= t
     }
 
+    /// Like |contains|, but excludes both endpoints.
+    #[must_use]
+    pub fn contains_interior(&self, t: &T) -> bool {
+        &self.st < t && &self.en > t
+    }
+
     #[must_use]
     pub fn contains_span(&self, s: &Self) -> bool {
         self.st <= s.st && self.en >= s.en
@@ -79,6 +93,20 @@ impl<T: PartialOrd> SpanInc<T> {
 }
 
 impl<T: PartialOrd + Copy> SpanInc<T> {
+    /// Clamps |t| into the span: |st| if below it, |en| if above it, otherwise
+    /// |t|. Panics if the span is empty.
+    #[must_use]
+    pub fn clamp_value(&self, t: T) -> T {
+        assert!(!self.is_empty(), "can't clamp into an empty span");
+        if t < self.st {
+            self.st
+        } else if t > self.en {
+            self.en
+        } else {
+            t
+        }
+    }
+
     pub fn cover(a: &Self, b: &Self) -> Self {
         if a.is_empty() {
             *b
@@ -127,6 +155,56 @@ impl<T: Sub + EndpointConversion + Copy> SpanInc<T> {
     }
 }
 
+impl<T: Add<Output = T> + Sub<Output = T> + PartialOrd + Copy> SpanInc<T> {
+    /// Moves both endpoints inward by |amount|, or None if that would leave
+    /// the span empty.
+    #[must_use]
+    pub fn shrink(&self, amount: T) -> Option<Self> {
+        let span = Self::new(self.st + amount, self.en - amount);
+        if span.is_empty() {
+            None
+        } else {
+            Some(span)
+        }
+    }
+}
+
+impl<U: Copy, T: Add<U, Output = T>> Add<U> for SpanInc<T> {
+    type Output = SpanInc<T>;
+
+    fn add(self, other: U) -> Self::Output {
+        Self::new(self.st + other, self.en + other)
+    }
+}
+
+impl<U: Copy, T: AddAssign<U>> AddAssign<U> for SpanInc<T> {
+    fn add_assign(&mut self, other: U) {
+        self.st += other;
+        self.en += other;
+    }
+}
+
+impl<U: Copy, T: Sub<U, Output = T>> Sub<U> for SpanInc<T> {
+    type Output = SpanInc<T>;
+
+    fn sub(self, other: U) -> Self::Output {
+        Self::new(self.st - other, self.en - other)
+    }
+}
+
+impl<U: Copy, T: SubAssign<U>> SubAssign<U> for SpanInc<T> {
+    fn sub_assign(&mut self, other: U) {
+        self.st -= other;
+        self.en -= other;
+    }
+}
+
+impl<T: Copy> From<T> for SpanInc<T> {
+    fn from(p: T) -> Self {
+        Self::point(p)
+    }
+}
+
 impl<T: Copy> From<RangeInclusive<T>> for SpanInc<T> {
     fn from(r: RangeInclusive<T>) -> Self {
         Self::new(*r.start(), *r.end())
@@ -391,4 +469,63 @@ mod tests {
         assert_eq!(inc_3_5.size(), Some(3));
         assert_eq!(empty.size(), Some(0));
     }
+
+    #[test]
+    fn translate() {
+        let mut span = SpanInc::<i64>::new(0, 2);
+        assert_eq!(span + 5, SpanInc::new(5, 7));
+        assert_eq!(span + 5 - 5, span);
+        assert_eq!(span - 3, SpanInc::new(-3, -1));
+
+        span += 5;
+        assert_eq!(span, SpanInc::new(5, 7));
+        span -= 5;
+        assert_eq!(span, SpanInc::new(0, 2));
+    }
+
+    #[test]
+    fn default_is_empty() {
+        assert!(SpanInc::<i64>::default().is_empty());
+        assert_eq!(SpanInc::<i64>::default(), SpanInc::empty().unwrap());
+        assert!(SpanInc::<u64>::default().is_empty());
+        assert!(SpanExc::<i64>::default().is_empty());
+        assert!(SpanExc::<u64>::default().is_empty());
+    }
+
+    #[test]
+    fn contains_interior() {
+        let s = SpanInc::new(0, 2);
+        assert!(s.contains(&0));
+        assert!(!s.contains_interior(&0));
+        assert!(s.contains_interior(&1));
+        assert!(s.contains(&2));
+        assert!(!s.contains_interior(&2));
+        assert!(!SpanInc::new(1, 1).contains_interior(&1));
+    }
+
+    #[test]
+    fn clamp_value() {
+        let s = SpanInc::new(0, 5);
+        assert_eq!(s.clamp_value(-3), 0);
+        assert_eq!(s.clamp_value(3), 3);
+        assert_eq!(s.clamp_value(5), 5);
+        assert_eq!(s.clamp_value(100), 5);
+    }
+
+    #[test]
+    fn from_point() {
+        assert_eq!(SpanInc::from(5), SpanInc::new(5, 5));
+        assert!(SpanInc::from(5).contains(&5));
+        let s: SpanInc<i64> = 7.into();
+        assert_eq!(s, SpanInc::point(7));
+    }
+
+    #[test]
+    fn shrink() {
+        assert_eq!(SpanInc::new(0, 10).shrink(3), Some(SpanInc::new(3, 7)));
+        // A single point is still non-empty for an inclusive span.
+        assert_eq!(SpanInc::new(0, 10).shrink(5), Some(SpanInc::new(5, 5)));
+        assert_eq!(SpanInc::new(0, 10).shrink(6), None);
+        assert_eq!(SpanInc::new(0, 3).shrink(2), None);
+    }
 }
@@ -3,8 +3,11 @@ use std::fmt;
 use std::str::FromStr;
 
 use auto_ops::impl_op_ex;
-use chrono::{DateTime, Datelike, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
-use chrono_tz::{Tz, UTC};
+use chrono::{
+    DateTime, Datelike, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+    Timelike, Utc,
+};
+use chrono_tz::{OffsetComponents, Tz, UTC};
 use derive_more::Display;
 use eyre::{eyre, Result};
 use num_traits::ToPrimitive;
@@ -17,6 +20,7 @@ use crate::date::{Date, Day};
 use crate::duration::Duration;
 use crate::op::{TOp, TimeOp};
 use crate::span::endpoint::EndpointConversion;
+use crate::span::exc::SpanExc;
 
 pub fn ymdhms<T: Borrow<Tz>>(
     year: i32,
@@ -55,6 +59,11 @@ impl Time {
         Time::from_utc_timestamp(0, 0, tz)
     }
 
+    /// The Unix epoch in |tz|. Same as |zero|.
+    pub fn unix_epoch(tz: Tz) -> Self {
+        Self::zero(tz)
+    }
+
     pub fn from_naive_date(d: NaiveDate, tz: Tz) -> Result<Self> {
         Date::new(d, tz).time()
     }
@@ -65,9 +74,17 @@ impl Time {
     }
 
     pub fn from_utc_dec(utc_dec: Decimal, tz: Tz) -> Self {
-        let utc_secs = utc_dec.trunc();
+        Self::checked_from_utc_dec(utc_dec, tz).unwrap()
+    }
+
+    /// Returns None if the time is out of the representable range.
+    #[must_use]
+    pub fn checked_from_utc_dec(utc_dec: Decimal, tz: Tz) -> Option<Self> {
+        // Floor so the nanoseconds are non-negative for times before the epoch.
+        let utc_secs = utc_dec.floor();
         let utc_nanos = ((utc_dec - utc_secs) * dec!(1000000000)).trunc();
-        Self::from_utc_timestamp(utc_secs.to_i64().unwrap(), utc_nanos.to_u32().unwrap(), tz)
+        let dt = NaiveDateTime::from_timestamp_opt(utc_secs.to_i64()?, utc_nanos.to_u32()?)?;
+        Some(tz.from_utc_datetime(&dt).into())
     }
 
     pub fn from_utc_f64(utc_f64: f64, tz: Tz) -> Self {
@@ -105,6 +122,13 @@ impl Time {
         Ok(Self::from_utc_timestamp(t.timestamp(), t.timestamp_subsec_nanos(), tz))
     }
 
+    /// From an ISO RFC3339 time with an offset but no timezone name. An offset
+    /// doesn't identify a timezone (e.g. +10:00 could be Brisbane or Sydney in
+    /// winter), so the result is always viewed in UTC.
+    pub fn from_iso_offset_tz(s: &str) -> Result<Self> {
+        Self::from_local_iso(s, UTC)
+    }
+
     /// From a local time.
     pub fn from_local(s: &str, tz: Tz) -> Result<Self> {
         Self::from_local_datetime_fmt(s, Self::LOCAL_FMT, tz)
@@ -118,10 +142,39 @@ impl Time {
         Ok(Self::new(dt))
     }
 
+    /// Like |ymdhms|, but errors instead of panicking on invalid components or
+    /// local times which don't exist or are ambiguous (because of daylight
+    /// savings etc).
+    pub fn from_ymdhms(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        min: u32,
+        sec: u32,
+        tz: Tz,
+    ) -> Result<Self> {
+        let d = NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|d| d.and_hms_opt(hour, min, sec))
+            .ok_or_else(|| {
+                eyre!("invalid date time {}-{}-{} {}:{}:{}", year, month, day, hour, min, sec)
+            })?;
+        Self::from_local_datetime(d, tz)
+    }
+
     pub fn from_local_datetime_fmt(s: &str, fmt: &str, tz: Tz) -> Result<Self> {
         Self::from_local_datetime(NaiveDateTime::parse_from_str(s, fmt)?, tz)
     }
 
+    /// Tries, in order: an RFC3339 instant, a local time in `LOCAL_FMT`, and
+    /// a date (at midnight) e.g. 2020-01-30 or 2020/01/30.
+    pub fn parse_flexible(s: &str, tz: Tz) -> Result<Self> {
+        Self::from_local_iso(s, tz)
+            .or_else(|_| Self::from_local(s, tz))
+            .or_else(|_| Self::from_ymd(s, tz))
+            .map_err(|_| eyre!("could not parse '{}' as an RFC3339 time, local time, or date", s))
+    }
+
     #[must_use]
     pub fn to_iso(&self) -> String {
         self.t.to_rfc3339()
@@ -151,6 +204,12 @@ impl Time {
         (wtz.timestamp(), wtz.timestamp_subsec_nanos())
     }
 
+    /// Returns true if this is the Unix epoch, in any timezone.
+    #[must_use]
+    pub fn is_epoch(&self) -> bool {
+        self.utc_timestamp() == (0, 0)
+    }
+
     #[must_use]
     pub fn utc_dec(&self) -> Decimal {
         let (secs, nanos) = self.utc_timestamp();
@@ -166,6 +225,18 @@ impl Time {
         self.t.with_timezone(&tz).into()
     }
 
+    /// Offset of local time from UTC, including any daylight savings.
+    pub fn utc_offset(&self) -> Duration {
+        i64::from(self.t.offset().fix().local_minus_utc()) * Duration::SEC
+    }
+
+    /// Whether daylight savings is in effect, i.e. the offset differs from
+    /// the standard offset for the timezone.
+    #[must_use]
+    pub fn is_dst(&self) -> bool {
+        self.t.offset().dst_offset() != chrono::Duration::zero()
+    }
+
     pub fn ymd(&self) -> Result<Self> {
         self.date().time()
     }
@@ -231,6 +302,27 @@ impl Time {
 
 /// Time and date operations
 impl Time {
+    /// Returns None instead of panicking if the result is out of range.
+    #[must_use]
+    pub fn checked_add(&self, d: Duration) -> Option<Time> {
+        Self::checked_from_utc_dec(self.utc_dec().checked_add(d.secs())?, self.tz())
+    }
+
+    /// Returns None instead of panicking if the result is out of range.
+    #[must_use]
+    pub fn checked_sub(&self, d: Duration) -> Option<Time> {
+        Self::checked_from_utc_dec(self.utc_dec().checked_sub(d.secs())?, self.tz())
+    }
+
+    /// Floors to the start of the |step| sized bucket containing this time,
+    /// where buckets are aligned to |anchor| rather than the epoch. Keeps
+    /// this time's timezone.
+    pub fn floor_to_from(&self, step: Duration, anchor: Time) -> Self {
+        assert!(step > Duration::zero(), "step {step} must be positive");
+        let n = ((*self - anchor) / step).floor();
+        (anchor + n * step).with_tz(self.tz())
+    }
+
     /// Returns a time with the given date. If the time of day doesn't
     /// exist on that date (because of daylight savings etc), returns
     /// the next time that does exist.
@@ -257,10 +349,67 @@ impl Time {
         }
     }
 
+    /// Returns the next time after this one with local time of day |tod|:
+    /// today if it's still ahead, otherwise tomorrow. If |tod| falls in a
+    /// daylight savings gap, returns the next time that does exist.
+    pub fn next_local_time(&self, tod: Duration) -> Result<Self> {
+        if tod < Duration::zero() || tod >= Duration::DAY {
+            return Err(eyre!("time of day {} must be in [0s, 1d)", tod));
+        }
+        let secs = tod.secs().trunc();
+        let nanos = ((tod.secs() - secs) * dec!(1000000000)).trunc();
+        let tod = NaiveTime::from_num_seconds_from_midnight_opt(
+            secs.to_u32().unwrap(),
+            nanos.to_u32().unwrap(),
+        )
+        .ok_or_else(|| eyre!("invalid time of day {}", tod))?;
+
+        let at_date = |d: NaiveDate| {
+            let mut localdt = d.and_time(tod);
+            loop {
+                match self.tz().from_local_datetime(&localdt) {
+                    LocalResult::None => {}
+                    LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => {
+                        return Self::new(dt);
+                    }
+                };
+                // Same as |with_date|, skip past any gap a minute at a time.
+                localdt += chrono::Duration::minutes(1);
+                localdt = localdt.with_second(0).unwrap();
+            }
+        };
+        let today = at_date(self.date().inner());
+        Ok(if today > *self { today } else { at_date(self.date().add_days(1).inner()) })
+    }
+
     pub fn with_nanos(&self, ns: u32) -> Self {
         self.t.with_nanosecond(ns).unwrap().into()
     }
 
+    /// Like |with_nanos|, but returns None if |ns| isn't less than a second
+    /// instead of panicking.
+    #[must_use]
+    pub fn with_nanos_checked(&self, ns: u32) -> Option<Self> {
+        if ns >= 1_000_000_000 {
+            return None;
+        }
+        self.t.with_nanosecond(ns).map(Into::into)
+    }
+
+    /// The next representable time, one nanosecond later. None at chrono's
+    /// upper bound.
+    #[must_use]
+    pub fn succ(&self) -> Option<Self> {
+        self.to_open(false)
+    }
+
+    /// The previous representable time, one nanosecond earlier. None at
+    /// chrono's lower bound.
+    #[must_use]
+    pub fn pred(&self) -> Option<Self> {
+        self.to_open(true)
+    }
+
     pub fn add_nanos(&self, ns: i64) -> Self {
         (self.t + chrono::Duration::nanoseconds(ns)).into()
     }
@@ -364,6 +513,16 @@ impl_op_ex!(-= |a: &mut Time, b: &Duration| { *a = *a - b });
 impl_op_ex!(+ |a: &Time, b: &Duration| -> Time { Time::from_utc_dec(a.utc_dec() + b.secs(), a.t.timezone()) });
 impl_op_ex!(+= |a: &mut Time, b: &Duration| { *a = *a + b });
 
+impl_op_ex!(+|a: &Time, b: &chrono::Duration| -> Time {
+    a.t.checked_add_signed(*b).expect("time out of range").into()
+});
+impl_op_ex!(+= |a: &mut Time, b: &chrono::Duration| { *a = *a + b });
+
+impl_op_ex!(-|a: &Time, b: &chrono::Duration| -> Time {
+    a.t.checked_sub_signed(*b).expect("time out of range").into()
+});
+impl_op_ex!(-= |a: &mut Time, b: &chrono::Duration| { *a = *a - b });
+
 impl<'a> Deserialize<'a> for Time {
     fn deserialize<D: serde::Deserializer<'a>>(d: D) -> Result<Self, D::Error> {
         struct TimeVisitor;
@@ -384,7 +543,23 @@ impl<'a> Deserialize<'a> for Time {
                     s.next().ok_or_else(|| eyre!("missing timestamp")).map_err(E::custom)?;
                 let tz = s.next().ok_or_else(|| eyre!("missing timezone")).map_err(E::custom)?;
                 let tz = Tz::from_str(tz).map_err(E::custom)?;
-                let time = Time::from_local(local, tz).map_err(E::custom)?;
+                // Also accept RFC3339 with an offset, which can represent ambiguous
+                // local times, but the offset must agree with the timezone.
+                let Ok(dt) = DateTime::parse_from_rfc3339(local) else {
+                    return Time::from_local(local, tz).map_err(E::custom);
+                };
+                let time =
+                    Time::from_utc_timestamp(dt.timestamp(), dt.timestamp_subsec_nanos(), tz);
+                let offset = i64::from(dt.offset().local_minus_utc()) * Duration::SEC;
+                if time.utc_offset() != offset {
+                    return Err(E::custom(eyre!(
+                        "offset {} of {} doesn't match {} offset {}",
+                        offset,
+                        local,
+                        tz.name(),
+                        time.utc_offset()
+                    )));
+                }
                 Ok(time)
             }
         }
@@ -395,7 +570,8 @@ impl<'a> Deserialize<'a> for Time {
 
 impl Serialize for Time {
     fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
-        s.serialize_str(&(self.to_local() + " " + self.tz().name()))
+        // Include the offset so ambiguous local times round trip.
+        s.serialize_str(&(self.to_iso() + " " + self.tz().name()))
     }
 }
 
@@ -427,6 +603,21 @@ impl EndpointConversion for Time {
     }
 }
 
+impl SpanExc<Time> {
+    /// Number of distinct local calendar days, in the timezone of |st|, that
+    /// this span touches. Zero if the span is empty.
+    #[must_use]
+    pub fn num_days_touched(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let first = self.st.date();
+        let last =
+            self.en.to_closed(false).expect("span end out of range").with_tz(self.st.tz()).date();
+        (last.inner() - first.inner()).num_days() as usize + 1
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono_tz::Australia::Sydney;
@@ -496,13 +687,68 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parse_flexible() -> Result<()> {
+        let dt = ymdhms(2018, 1, 30, 6, 4, 57, Sydney);
+        assert_eq!(dt, Time::parse_flexible("2018-01-29T19:04:57Z", Sydney)?);
+        assert_eq!(dt, Time::parse_flexible("2018-01-30T06:04:57", Sydney)?);
+        assert_eq!(ymd(2018, 1, 30, Sydney).time()?, Time::parse_flexible("2018-01-30", Sydney)?);
+        assert_eq!(ymd(2018, 1, 30, Sydney).time()?, Time::parse_flexible("2018/01/30", Sydney)?);
+
+        let err = Time::parse_flexible("30 Jan 2018", Sydney).unwrap_err();
+        assert!(err.to_string().contains("30 Jan 2018"));
+        Ok(())
+    }
+
     #[test]
     fn serialization() -> Result<()> {
         let dt = ymdhms(2018, 1, 30, 6, 4, 57, Sydney);
         let se = serde_json::to_string(&dt)?;
-        assert_eq!(se, "\"2018-01-30T06:04:57 Australia/Sydney\"");
+        assert_eq!(se, "\"2018-01-30T06:04:57+11:00 Australia/Sydney\"");
         let de: Time = serde_json::from_str(&se)?;
         assert_eq!(de, dt);
+        assert_eq!(de.tz(), Sydney);
+        Ok(())
+    }
+
+    #[test]
+    fn serialization_ambiguous_round_trip() -> Result<()> {
+        // 01:30 happens twice on 2020-11-01 in US/Eastern, first in EDT then EST.
+        let edt = ymdhms(2020, 11, 1, 5, 30, 0, UTC).with_tz(Eastern);
+        let est = ymdhms(2020, 11, 1, 6, 30, 0, UTC).with_tz(Eastern);
+        assert_eq!(edt.to_local(), est.to_local());
+
+        let se = serde_json::to_string(&edt)?;
+        assert_eq!(se, "\"2020-11-01T01:30:00-04:00 US/Eastern\"");
+        let de: Time = serde_json::from_str(&se)?;
+        assert_eq!(de, edt);
+        assert_eq!(de.tz(), Eastern);
+
+        let se = serde_json::to_string(&est)?;
+        assert_eq!(se, "\"2020-11-01T01:30:00-05:00 US/Eastern\"");
+        let de: Time = serde_json::from_str(&se)?;
+        assert_eq!(de, est);
+        assert_eq!(de.tz(), Eastern);
+
+        let de: Time = serde_json::from_str(&serde_json::to_string(&edt.add_nanos(5))?)?;
+        assert_eq!(de, edt.add_nanos(5));
+        Ok(())
+    }
+
+    #[test]
+    fn deserialization_with_offset() -> Result<()> {
+        let de: Time = serde_json::from_str("\"2018-01-30T06:04:57+11:00 Australia/Sydney\"")?;
+        assert_eq!(de, ymdhms(2018, 1, 30, 6, 4, 57, Sydney));
+        assert_eq!(de.tz(), Sydney);
+
+        // Ambiguous local time when clocks go back is resolved by the offset.
+        let edt: Time = serde_json::from_str("\"2020-11-01T01:30:00-04:00 US/Eastern\"")?;
+        let est: Time = serde_json::from_str("\"2020-11-01T01:30:00-05:00 US/Eastern\"")?;
+        assert_eq!(est - edt, Duration::HOUR);
+        assert!(serde_json::from_str::<Time>("\"2020-11-01T01:30:00 US/Eastern\"").is_err());
+
+        let err = serde_json::from_str::<Time>("\"2018-01-30T06:04:57+10:00 Australia/Sydney\"");
+        assert!(err.unwrap_err().to_string().contains("doesn't match"));
         Ok(())
     }
 
@@ -512,4 +758,241 @@ mod tests {
         // This shouldn't change the underlying time, just the timezone it's in.
         assert_eq!(time.utc_dec(), time.with_tz(Eastern).utc_dec());
     }
+
+    #[test]
+    fn utc_offset() {
+        let summer = ymdhms(2020, 7, 1, 12, 0, 0, Eastern);
+        assert_eq!(summer.utc_offset(), -4 * Duration::HOUR);
+        assert!(summer.is_dst());
+
+        let winter = ymdhms(2020, 1, 1, 12, 0, 0, Eastern);
+        assert_eq!(winter.utc_offset(), -5 * Duration::HOUR);
+        assert!(!winter.is_dst());
+
+        let utc = ymdhms(2020, 7, 1, 12, 0, 0, UTC);
+        assert_eq!(utc.utc_offset(), Duration::zero());
+        assert!(!utc.is_dst());
+    }
+
+    #[test]
+    fn checked_add_sub() {
+        let t = ymdhms(2020, 1, 1, 12, 0, 0, Eastern);
+        assert_eq!(t.checked_add(Duration::DAY), Some(ymdhms(2020, 1, 2, 12, 0, 0, Eastern)));
+        assert_eq!(t.checked_sub(Duration::DAY), Some(ymdhms(2019, 12, 31, 12, 0, 0, Eastern)));
+        assert_eq!(
+            Time::zero(UTC).checked_sub(Duration::MSEC),
+            Some(ymdhms(1969, 12, 31, 23, 59, 59, UTC).with_millis(999))
+        );
+
+        let huge = Duration::new(dec!(1000000000000000));
+        assert_eq!(t.checked_add(huge), None);
+        assert_eq!(t.checked_sub(huge), None);
+        let huger = Duration::new(Decimal::MAX);
+        assert_eq!(t.checked_add(huger), None);
+        assert_eq!(t.checked_sub(huger), None);
+    }
+
+    #[test]
+    fn from_iso_offset_tz() -> Result<()> {
+        let t = Time::from_iso_offset_tz("2020-01-02T03:04:05+00:00")?;
+        assert_eq!(t, ymdhms(2020, 1, 2, 3, 4, 5, UTC));
+        assert_eq!(t.tz(), UTC);
+
+        let t = Time::from_iso_offset_tz("2020-01-02T03:04:05+11:00")?;
+        assert_eq!(t, ymdhms(2020, 1, 2, 3, 4, 5, Sydney));
+        assert_eq!(t.tz(), UTC);
+        assert_eq!(t.hour(), 16);
+
+        assert!(Time::from_iso_offset_tz("2020-01-02T03:04:05").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn floor_to_from() {
+        let anchor = ymdhms(2020, 1, 2, 9, 30, 0, Eastern);
+        let step = 5 * Duration::MIN;
+        let t = |h, m, s| ymdhms(2020, 1, 2, h, m, s, Eastern);
+        assert_eq!(t(9, 33, 0).floor_to_from(step, anchor), t(9, 30, 0));
+        assert_eq!(t(9, 37, 0).floor_to_from(step, anchor), t(9, 35, 0));
+        assert_eq!(t(9, 35, 0).floor_to_from(step, anchor), t(9, 35, 0));
+        assert_eq!(t(9, 29, 59).floor_to_from(step, anchor), t(9, 25, 0));
+        // Anchors on other days and in other timezones work the same.
+        let anchor = ymdhms(2020, 1, 1, 14, 30, 0, UTC);
+        let floored = t(9, 37, 0).floor_to_from(step, anchor);
+        assert_eq!(floored, t(9, 35, 0));
+        assert_eq!(floored.tz(), Eastern);
+    }
+
+    #[test]
+    fn from_ymdhms() -> Result<()> {
+        assert_eq!(
+            Time::from_ymdhms(2020, 1, 2, 3, 4, 5, Eastern)?,
+            ymdhms(2020, 1, 2, 3, 4, 5, Eastern)
+        );
+        assert!(Time::from_ymdhms(2020, 2, 30, 0, 0, 0, Eastern).is_err());
+        assert!(Time::from_ymdhms(2020, 1, 1, 24, 0, 0, Eastern).is_err());
+        // Clocks go forward from 2am to 3am.
+        assert!(Time::from_ymdhms(2020, 3, 8, 2, 30, 0, Eastern).is_err());
+        // Clocks go back from 2am to 1am.
+        assert!(Time::from_ymdhms(2020, 11, 1, 1, 30, 0, Eastern).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn unix_epoch() {
+        assert!(Time::unix_epoch(UTC).is_epoch());
+        assert!(Time::unix_epoch(Sydney).is_epoch());
+        assert_eq!(Time::unix_epoch(Eastern), Time::zero(Eastern));
+        assert_eq!(Time::unix_epoch(Eastern).tz(), Eastern);
+        assert!(!(Time::unix_epoch(UTC) + Duration::SEC).is_epoch());
+        assert!(!(Time::unix_epoch(UTC) - Duration::NSEC).is_epoch());
+    }
+
+    #[test]
+    fn succ_pred() {
+        let t = ymdhms(2020, 1, 1, 12, 0, 0, Eastern);
+        assert_eq!(t.succ(), Some(t.add_nanos(1)));
+        assert_eq!(t.pred(), Some(t.add_nanos(-1)));
+        assert_eq!(t.succ().unwrap() - t, Duration::NSEC);
+        assert_eq!(t.succ().unwrap().pred(), Some(t));
+        assert_eq!(t.succ().unwrap().tz(), Eastern);
+
+        let max = Time::new(UTC.from_utc_datetime(&NaiveDateTime::MAX));
+        let min = Time::new(UTC.from_utc_datetime(&NaiveDateTime::MIN));
+        assert_eq!(max.succ(), None);
+        assert!(max.pred().is_some());
+        assert_eq!(min.pred(), None);
+        assert!(min.succ().is_some());
+    }
+
+    #[test]
+    fn with_nanos_checked() {
+        let t = ymdhms(2020, 1, 1, 12, 0, 0, Eastern);
+        assert_eq!(t.with_nanos_checked(2_000_000_000), None);
+        assert_eq!(t.with_nanos_checked(1_000_000_000), None);
+        assert_eq!(t.with_nanos_checked(999_999_999), Some(t.with_nanos(999_999_999)));
+        assert_eq!(t.with_nanos_checked(5), Some(t.add_nanos(5)));
+    }
+
+    #[test]
+    fn chrono_duration_ops() {
+        let t = ymdhms(2020, 1, 1, 12, 0, 0, Eastern);
+        assert_eq!(t + chrono::Duration::hours(1), t + Duration::HOUR);
+        assert_eq!(t - chrono::Duration::hours(1), t - Duration::HOUR);
+        assert_eq!((t + chrono::Duration::milliseconds(1500)).tz(), Eastern);
+        assert_eq!(t + chrono::Duration::milliseconds(1500), t + 1500 * Duration::MSEC);
+
+        let mut t2 = t;
+        t2 += chrono::Duration::days(1);
+        assert_eq!(t2, ymdhms(2020, 1, 2, 12, 0, 0, Eastern));
+        t2 -= chrono::Duration::days(1);
+        assert_eq!(t2, t);
+    }
+
+    #[test]
+    fn min_max_clamp() {
+        let a = ymdhms(2020, 1, 1, 9, 0, 0, Eastern);
+        let b = ymdhms(2020, 1, 1, 9, 0, 0, UTC);
+        assert_eq!(a.min(b), b);
+        assert_eq!(a.min(b).tz(), UTC);
+        assert_eq!(a.max(b), a);
+        assert_eq!(a.max(b).tz(), Eastern);
+
+        let lo = ymdhms(2020, 1, 1, 10, 0, 0, Sydney);
+        let hi = ymdhms(2020, 1, 2, 10, 0, 0, Sydney);
+        assert_eq!(a.clamp(lo, hi), a);
+        assert_eq!(a.clamp(lo, hi).tz(), Eastern);
+        assert_eq!(ymdhms(2019, 1, 1, 0, 0, 0, UTC).clamp(lo, hi).tz(), Sydney);
+        assert_eq!(ymdhms(2019, 1, 1, 0, 0, 0, UTC).clamp(lo, hi), lo);
+        assert_eq!(ymdhms(2021, 1, 1, 0, 0, 0, UTC).clamp(lo, hi), hi);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clamp_invalid_range() {
+        let t = ymdhms(2020, 1, 1, 9, 0, 0, UTC);
+        let _ = t.clamp(t + Duration::SEC, t);
+    }
+
+    #[test]
+    fn compare_across_timezones() {
+        use std::cmp::Ordering;
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashSet;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |t: &Time| {
+            let mut h = DefaultHasher::new();
+            t.hash(&mut h);
+            h.finish()
+        };
+
+        let utc = ymdhms(2020, 1, 1, 17, 0, 0, UTC);
+        let eastern = utc.with_tz(Eastern);
+        assert_eq!(eastern.hour(), 12);
+        assert_eq!(utc.cmp(&eastern), Ordering::Equal);
+        assert_eq!(utc, eastern);
+        assert_eq!(hash(&utc), hash(&eastern));
+        assert_eq!(utc - eastern, Duration::zero());
+
+        // The same instant in different timezones deduplicates.
+        let set: HashSet<Time> = [utc, eastern, utc.with_tz(Sydney)].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(&ymdhms(2020, 1, 2, 4, 0, 0, Sydney)));
+        assert!(!set.contains(&ymdhms(2020, 1, 1, 17, 0, 0, Sydney)));
+
+        let later = ymdhms(2020, 1, 1, 13, 0, 0, Eastern);
+        assert_eq!(utc.cmp(&later), Ordering::Less);
+        assert_eq!(later - utc, Duration::HOUR);
+    }
+
+    #[test]
+    fn next_local_time() -> Result<()> {
+        let tod = 9 * Duration::HOUR + 30 * Duration::MIN;
+        assert_eq!(
+            ymdhms(2020, 1, 1, 8, 0, 0, Eastern).next_local_time(tod)?,
+            ymdhms(2020, 1, 1, 9, 30, 0, Eastern)
+        );
+        assert_eq!(
+            ymdhms(2020, 1, 1, 10, 0, 0, Eastern).next_local_time(tod)?,
+            ymdhms(2020, 1, 2, 9, 30, 0, Eastern)
+        );
+        assert_eq!(
+            ymdhms(2020, 1, 1, 9, 30, 0, Eastern).next_local_time(tod)?,
+            ymdhms(2020, 1, 2, 9, 30, 0, Eastern)
+        );
+        // 2:30 doesn't exist on the day clocks go forward.
+        assert_eq!(
+            ymdhms(2020, 3, 8, 1, 0, 0, Eastern)
+                .next_local_time(2 * Duration::HOUR + 30 * Duration::MIN)?,
+            ymdhms(2020, 3, 8, 3, 0, 0, Eastern)
+        );
+        assert!(Time::zero(UTC).next_local_time(Duration::DAY).is_err());
+        assert!(Time::zero(UTC).next_local_time(-Duration::SEC).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn num_days_touched() {
+        let span =
+            SpanExc::new(ymdhms(2020, 1, 1, 23, 0, 0, UTC), ymdhms(2020, 1, 2, 1, 0, 0, UTC));
+        assert_eq!(span.num_days_touched(), 2);
+        let span =
+            SpanExc::new(ymdhms(2020, 1, 1, 9, 0, 0, UTC), ymdhms(2020, 1, 1, 17, 0, 0, UTC));
+        assert_eq!(span.num_days_touched(), 1);
+        // Ending exactly at midnight doesn't touch the next day.
+        let span = SpanExc::new(ymdhms(2020, 1, 1, 9, 0, 0, UTC), ymdhms(2020, 1, 2, 0, 0, 0, UTC));
+        assert_eq!(span.num_days_touched(), 1);
+        let span = SpanExc::new(ymdhms(2020, 1, 1, 9, 0, 0, UTC), ymdhms(2020, 1, 1, 9, 0, 0, UTC));
+        assert_eq!(span.num_days_touched(), 0);
+
+        // Days are counted in the timezone of the start. 2020-04-05 has a 25
+        // hour day in Sydney as DST ends.
+        let span =
+            SpanExc::new(ymdhms(2020, 4, 5, 0, 0, 0, Sydney), ymdhms(2020, 4, 6, 0, 0, 0, Sydney));
+        assert_eq!(span.num_days_touched(), 1);
+        let span =
+            SpanExc::new(ymdhms(2020, 1, 1, 0, 0, 0, Sydney), ymdhms(2020, 1, 1, 14, 0, 0, UTC));
+        assert_eq!(span.num_days_touched(), 2);
+    }
 }